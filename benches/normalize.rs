@@ -0,0 +1,115 @@
+use am_algorithm::{
+    AnimeTitle, ExpectAnime, MatchIndex, MatcherConfig, Normalizer, Pruning, find_best_match, sanitize_title,
+};
+use criterion::{BenchmarkId, Criterion, black_box, criterion_group, criterion_main};
+use regex::Regex;
+use unicode_normalization::UnicodeNormalization;
+
+const TITLES: [&str; 8] = [
+    "Attack on Titan Season 3 Part 2",
+    "Boku no Hero Academia 2nd Season",
+    "Steins;Gate 0 (TV)",
+    "Hagane no Renkinjutsushi: Fullmetal Alchemist",
+    "Naruto: Shippuuden (Uncut) BD",
+    "Bleach: Sennen Kessen-hen - Ketsubetsu-tan",
+    "Yuu Yuu Hakusho",
+    "進撃の巨人 The Final Season",
+];
+
+/// The sanitizer as it was before patterns were compiled once, for comparison
+fn sanitize_uncompiled(title: &str) -> String {
+    let mut sanitized = title.to_lowercase();
+    sanitized = sanitized.replace("chapters", "chapter");
+    let season_regex = Regex::new(r"\b(season|cour|part|chapter|special)\b").unwrap();
+    sanitized = season_regex.replace_all(&sanitized, "").to_string();
+    let numbered_season_regex =
+        Regex::new(r"(\d+)(?:th|rd|nd|st)?\s*(?:season|cour|part|chapter|special)\b").unwrap();
+    sanitized = numbered_season_regex.replace_all(&sanitized, " $1 ").to_string();
+    sanitized = sanitized
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c.is_whitespace() { c } else { ' ' })
+        .collect();
+    sanitized = sanitized.replace("yuu", "yu");
+    sanitized = sanitized.replace("ouh", "oh");
+    sanitized = sanitized.replace("yaa", "ya");
+    let format_regex = Regex::new(
+        r"\b(?:uncut|uncensored|dub(?:bed)?|censored|sub(?:bed)?|the final chapters)\b|\([^)]*\)|\bBD\b|\(TV\)",
+    )
+    .unwrap();
+    sanitized = format_regex.replace_all(&sanitized, "").to_string();
+    sanitized = sanitized
+        .nfd()
+        .filter(|c| !unicode_categories::UnicodeCategories::is_mark(*c))
+        .collect();
+    am_algorithm::clean_title(Some(&sanitized)).unwrap()
+}
+
+fn catalogue(size: usize) -> Vec<ExpectAnime> {
+    (0..size)
+        .map(|i| {
+            let base = TITLES[i % TITLES.len()];
+            ExpectAnime {
+                id: Some(serde_json::json!(i)),
+                title: Some(AnimeTitle {
+                    english: Some(format!("{base} {i}")),
+                    romaji: Some(format!("{base} romaji {i}")),
+                    native: None,
+                    user_preferred: Some(format!("{base} {i}")),
+                    synonyms: Vec::new(),
+                }),
+                year: Some(1990 + (i % 35) as i32),
+                episodes: Some(12 + (i % 40) as i32),
+                format: None,
+            }
+        })
+        .collect()
+}
+
+fn sanitize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sanitize");
+    let normalizer = Normalizer::new();
+
+    group.bench_function("uncompiled", |b| {
+        b.iter(|| TITLES.iter().map(|t| sanitize_uncompiled(black_box(t))).collect::<Vec<_>>())
+    });
+    group.bench_function("sanitize_title", |b| {
+        b.iter(|| TITLES.iter().filter_map(|t| sanitize_title(Some(black_box(t)))).collect::<Vec<_>>())
+    });
+    group.bench_function("normalizer", |b| {
+        b.iter(|| TITLES.iter().map(|t| normalizer.sanitize(black_box(t))).collect::<Vec<_>>())
+    });
+
+    group.finish();
+}
+
+fn search(c: &mut Criterion) {
+    let mut group = c.benchmark_group("search");
+    let config = MatcherConfig::default();
+    // Misspelled so the search falls through to the fuzzy tiers
+    let search = ExpectAnime::from_string_title("Atack on Titan Season 3 Part 2".into(), None, None);
+
+    for size in [100, 1_000] {
+        let catalogue = catalogue(size);
+        let normalized = Normalizer::new().normalize_catalogue(&catalogue, |anime| anime);
+        let index = MatchIndex::new(normalized.clone());
+        let pruned = MatchIndex::new(normalized.clone()).with_pruning(Pruning { shortlist: 50, n: 3 });
+
+        group.bench_with_input(BenchmarkId::new("find_best_match", size), &catalogue, |b, catalogue| {
+            b.iter(|| find_best_match(black_box(&search), catalogue, |anime| anime))
+        });
+        group.bench_with_input(BenchmarkId::new("normalized_catalogue", size), &normalized, |b, normalized| {
+            b.iter(|| normalized.find_best_match(black_box(&search), &config))
+        });
+        group.bench_with_input(BenchmarkId::new("match_index", size), &index, |b, index| {
+            b.iter(|| index.find_best_match(black_box(&search), &config))
+        });
+        group.bench_with_input(BenchmarkId::new("pruned_index", size), &pruned, |b, pruned| {
+            b.iter(|| pruned.find_best_match(black_box(&search), &config))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, sanitize, search);
+criterion_main!(benches);
@@ -0,0 +1,27 @@
+use crate::config::MatcherConfig;
+use crate::index::MatchIndex;
+use crate::r#match::{ExpectAnime, MatchResult};
+
+/// Finds the best match for every search against one index, returning the
+/// results in the same order as `searches`.
+///
+/// With the `rayon` feature enabled, searches run in parallel on the global
+/// rayon thread pool, all sharing the index and its normalized titles.
+/// Without it they run one after another on the calling thread.
+pub fn match_batch<T: Clone + Send + Sync>(
+    searches: &[ExpectAnime],
+    index: &MatchIndex<'_, T>,
+    config: &MatcherConfig,
+) -> Vec<Option<MatchResult<T>>> {
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+
+        searches.par_iter().map(|search| index.find_best_match(search, config)).collect()
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    {
+        searches.iter().map(|search| index.find_best_match(search, config)).collect()
+    }
+}
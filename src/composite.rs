@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+
+use crate::format::{self, MediaFormat};
+
+/// Weights for scoring every candidate with a single composite score instead
+/// of running the tiered cascade.
+///
+/// Each component is scored between 0 and 1 and the composite is their
+/// weighted mean. Year, episode and format components are left out, and the
+/// remaining weights renormalized, when either side lacks the value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompositeScoring {
+    pub title_weight: f64,
+    pub year_weight: f64,
+    pub episodes_weight: f64,
+    pub format_weight: f64,
+    /// Year gap at which the year component drops to 0.
+    pub year_scale: f64,
+    /// Minimum composite score a candidate needs to be returned.
+    pub threshold: f64,
+}
+
+impl Default for CompositeScoring {
+    fn default() -> Self {
+        Self {
+            title_weight: 0.7,
+            year_weight: 0.2,
+            episodes_weight: 0.1,
+            format_weight: 0.1,
+            year_scale: 3.0,
+            threshold: 0.6,
+        }
+    }
+}
+
+/// How candidates are scored against a search.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ScoringMode {
+    /// The tiered cascade, where the first tier to match wins.
+    #[default]
+    Cascade,
+    /// One weighted score per candidate, highest wins.
+    Composite(CompositeScoring),
+}
+
+/// The components behind a composite score.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ScoreBreakdown {
+    pub title: f64,
+    pub year: Option<f64>,
+    pub episodes: Option<f64>,
+    pub format: Option<f64>,
+    pub composite: f64,
+}
+
+impl CompositeScoring {
+    /// Combines a title similarity with the year and episode proximity and the
+    /// format of a candidate into a composite score.
+    pub(crate) fn score(
+        &self,
+        title: f64,
+        search: (Option<i32>, Option<i32>, Option<MediaFormat>),
+        candidate: (Option<i32>, Option<i32>, Option<MediaFormat>),
+    ) -> ScoreBreakdown {
+        let year = match (search.0, candidate.0) {
+            (Some(a), Some(b)) => Some(year_proximity(a, b, self.year_scale)),
+            _ => None,
+        };
+        let episodes = match (search.1, candidate.1) {
+            (Some(a), Some(b)) => Some(episode_proximity(a, b)),
+            _ => None,
+        };
+        let format = format::compare(search.2, candidate.2).map(|same| if same { 1.0 } else { 0.0 });
+
+        let mut weighted = self.title_weight * title;
+        let mut total = self.title_weight;
+
+        if let Some(year) = year {
+            weighted += self.year_weight * year;
+            total += self.year_weight;
+        }
+        if let Some(episodes) = episodes {
+            weighted += self.episodes_weight * episodes;
+            total += self.episodes_weight;
+        }
+        if let Some(format) = format {
+            weighted += self.format_weight * format;
+            total += self.format_weight;
+        }
+
+        let composite = if total > 0.0 { weighted / total } else { 0.0 };
+
+        ScoreBreakdown {
+            title,
+            year,
+            episodes,
+            format,
+            composite,
+        }
+    }
+}
+
+/// 1 for the same year, falling linearly to 0 at `scale` years apart
+fn year_proximity(a: i32, b: i32, scale: f64) -> f64 {
+    let gap = a.abs_diff(b) as f64;
+    if scale <= 0.0 {
+        return if gap == 0.0 { 1.0 } else { 0.0 };
+    }
+    (1.0 - gap / scale).max(0.0)
+}
+
+/// Ratio of the smaller episode count to the larger one
+fn episode_proximity(a: i32, b: i32) -> f64 {
+    let (a, b) = (a.max(0) as f64, b.max(0) as f64);
+    let larger = a.max(b);
+    if larger == 0.0 {
+        return 1.0;
+    }
+    a.min(b) / larger
+}
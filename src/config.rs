@@ -0,0 +1,298 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::composite::ScoringMode;
+use crate::episodes::EpisodeTolerance;
+use crate::format::FormatMismatch;
+use crate::r#match::MatchMethod;
+use crate::normalize::Normalizer;
+use crate::season::SeasonMismatch;
+use crate::similarity::{JaroWinkler, Similarity};
+use crate::weights::TitleWeights;
+
+/// Tunes the matching cascade used by [`find_best_match_with`](crate::find_best_match_with).
+///
+/// The default configuration runs all ten tiers in their usual order with the
+/// usual cut-offs, so it behaves exactly like [`find_best_match`](crate::find_best_match).
+#[derive(Clone)]
+pub struct MatcherConfig {
+    tiers: Vec<MatchMethod>,
+    loose_year_threshold: f64,
+    loose_threshold: f64,
+    last_resort_threshold: f64,
+    null_method_threshold: f64,
+    transliterated_threshold: f64,
+    similarity: Arc<dyn Similarity>,
+    tier_similarities: Vec<(MatchMethod, Arc<dyn Similarity>)>,
+    scoring: ScoringMode,
+    year_tolerance: u32,
+    year_penalty: f64,
+    exact_penalty_floor: f64,
+    episode_tolerance: EpisodeTolerance,
+    normalizer: Normalizer,
+    title_weights: TitleWeights,
+    season_mismatch: SeasonMismatch,
+    format_mismatch: FormatMismatch,
+}
+
+impl fmt::Debug for MatcherConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MatcherConfig")
+            .field("tiers", &self.tiers)
+            .field("loose_year_threshold", &self.loose_year_threshold)
+            .field("loose_threshold", &self.loose_threshold)
+            .field("last_resort_threshold", &self.last_resort_threshold)
+            .field("null_method_threshold", &self.null_method_threshold)
+            .field("transliterated_threshold", &self.transliterated_threshold)
+            .field("similarity", &self.similarity.name())
+            .field(
+                "tier_similarities",
+                &self
+                    .tier_similarities
+                    .iter()
+                    .map(|(method, metric)| (method, metric.name()))
+                    .collect::<Vec<_>>(),
+            )
+            .field("scoring", &self.scoring)
+            .field("year_tolerance", &self.year_tolerance)
+            .field("year_penalty", &self.year_penalty)
+            .field("exact_penalty_floor", &self.exact_penalty_floor)
+            .field("episode_tolerance", &self.episode_tolerance)
+            .field("normalizer", &self.normalizer)
+            .field("title_weights", &self.title_weights)
+            .field("season_mismatch", &self.season_mismatch)
+            .field("format_mismatch", &self.format_mismatch)
+            .finish()
+    }
+}
+
+impl Default for MatcherConfig {
+    fn default() -> Self {
+        Self {
+            tiers: MatchMethod::ALL.to_vec(),
+            loose_year_threshold: 0.8,
+            loose_threshold: 0.8,
+            last_resort_threshold: 0.7,
+            null_method_threshold: 0.6,
+            transliterated_threshold: 0.8,
+            similarity: Arc::new(JaroWinkler::default()),
+            tier_similarities: Vec::new(),
+            scoring: ScoringMode::Cascade,
+            year_tolerance: 0,
+            year_penalty: 0.05,
+            exact_penalty_floor: 0.8,
+            episode_tolerance: EpisodeTolerance::default(),
+            normalizer: Normalizer::default(),
+            title_weights: TitleWeights::default(),
+            season_mismatch: SeasonMismatch::default(),
+            format_mismatch: FormatMismatch::default(),
+        }
+    }
+}
+
+impl MatcherConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the cascade with the given tiers, tried in the given order.
+    /// Tiers left out are disabled and duplicates are ignored.
+    pub fn with_tiers(mut self, tiers: impl IntoIterator<Item = MatchMethod>) -> Self {
+        self.tiers.clear();
+        for method in tiers {
+            if !self.tiers.contains(&method) {
+                self.tiers.push(method);
+            }
+        }
+        self
+    }
+
+    /// Appends a tier to the end of the cascade, unless it is already enabled.
+    /// This is how the optional [`MatchMethod::Transliterated`] tier is turned on.
+    pub fn with_tier(mut self, method: MatchMethod) -> Self {
+        if !self.tiers.contains(&method) {
+            self.tiers.push(method);
+        }
+        self
+    }
+
+    /// Removes a single tier from the cascade.
+    pub fn without_tier(mut self, method: MatchMethod) -> Self {
+        self.tiers.retain(|&m| m != method);
+        self
+    }
+
+    /// Minimum similarity for [`MatchMethod::LooseYear`].
+    pub fn with_loose_year_threshold(mut self, threshold: f64) -> Self {
+        self.loose_year_threshold = threshold;
+        self
+    }
+
+    /// Minimum similarity for [`MatchMethod::Loose`].
+    pub fn with_loose_threshold(mut self, threshold: f64) -> Self {
+        self.loose_threshold = threshold;
+        self
+    }
+
+    /// Minimum similarity for [`MatchMethod::LastResort`].
+    pub fn with_last_resort_threshold(mut self, threshold: f64) -> Self {
+        self.last_resort_threshold = threshold;
+        self
+    }
+
+    /// Minimum similarity for [`MatchMethod::NullMethod`].
+    pub fn with_null_method_threshold(mut self, threshold: f64) -> Self {
+        self.null_method_threshold = threshold;
+        self
+    }
+
+    /// Minimum similarity for [`MatchMethod::Transliterated`].
+    pub fn with_transliterated_threshold(mut self, threshold: f64) -> Self {
+        self.transliterated_threshold = threshold;
+        self
+    }
+
+    /// Sets the metric used by every fuzzy tier without its own override.
+    pub fn with_similarity(mut self, metric: impl Similarity + 'static) -> Self {
+        self.similarity = Arc::new(metric);
+        self
+    }
+
+    /// Sets the metric used by a single fuzzy tier.
+    pub fn with_tier_similarity(mut self, method: MatchMethod, metric: impl Similarity + 'static) -> Self {
+        self.tier_similarities.retain(|(m, _)| *m != method);
+        self.tier_similarities.push((method, Arc::new(metric)));
+        self
+    }
+
+    /// Switches between the tiered cascade and composite scoring.
+    pub fn with_scoring(mut self, scoring: ScoringMode) -> Self {
+        self.scoring = scoring;
+        self
+    }
+
+    /// Lets year-aware tiers accept candidates up to `years` away from the
+    /// searched year.
+    pub fn with_year_tolerance(mut self, years: u32) -> Self {
+        self.year_tolerance = years;
+        self
+    }
+
+    /// Similarity subtracted per year of difference in year-aware tiers.
+    pub fn with_year_penalty(mut self, penalty: f64) -> Self {
+        self.year_penalty = penalty;
+        self
+    }
+
+    /// Lowest similarity a year or format penalty may leave an exact tier's
+    /// match at. Exact matches pushed below it are rejected, so the cascade
+    /// moves on to the fuzzy tiers. 0.8 by default.
+    pub fn with_exact_penalty_floor(mut self, floor: f64) -> Self {
+        self.exact_penalty_floor = floor;
+        self
+    }
+
+    /// Loosens how episode-aware tiers compare episode counts.
+    pub fn with_episode_tolerance(mut self, tolerance: EpisodeTolerance) -> Self {
+        self.episode_tolerance = tolerance;
+        self
+    }
+
+    /// Sets the pipeline that sanitizes search and candidate titles.
+    pub fn with_normalizer(mut self, normalizer: Normalizer) -> Self {
+        self.normalizer = normalizer;
+        self
+    }
+
+    /// Weighs fuzzy matches by candidate title field and optionally restricts
+    /// them to titles of the same language.
+    pub fn with_title_weights(mut self, weights: TitleWeights) -> Self {
+        self.title_weights = weights;
+        self
+    }
+
+    /// Sets how title pairs naming different seasons, parts or cours are
+    /// treated by the normalized and fuzzy tiers. They are rejected by default.
+    pub fn with_season_mismatch(mut self, mismatch: SeasonMismatch) -> Self {
+        self.season_mismatch = mismatch;
+        self
+    }
+
+    /// Sets how candidates of another format than the searched one are
+    /// treated. By default they are kept but lose ties to same-format candidates.
+    pub fn with_format_mismatch(mut self, mismatch: FormatMismatch) -> Self {
+        self.format_mismatch = mismatch;
+        self
+    }
+
+    /// The enabled tiers, in the order they are tried.
+    pub fn tiers(&self) -> &[MatchMethod] {
+        &self.tiers
+    }
+
+    /// The minimum similarity a tier accepts. Exact tiers always require 1.0.
+    pub fn threshold(&self, method: MatchMethod) -> f64 {
+        match method {
+            MatchMethod::LooseYear => self.loose_year_threshold,
+            MatchMethod::Loose => self.loose_threshold,
+            MatchMethod::LastResort => self.last_resort_threshold,
+            MatchMethod::NullMethod => self.null_method_threshold,
+            MatchMethod::Transliterated => self.transliterated_threshold,
+            _ => 1.0,
+        }
+    }
+
+    /// How many years a year-aware tier lets a candidate be off by.
+    pub fn year_tolerance(&self) -> u32 {
+        self.year_tolerance
+    }
+
+    /// Similarity lost per year of difference.
+    pub fn year_penalty(&self) -> f64 {
+        self.year_penalty
+    }
+
+    /// Lowest similarity a penalized exact match is kept at.
+    pub fn exact_penalty_floor(&self) -> f64 {
+        self.exact_penalty_floor
+    }
+
+    /// How episode-aware tiers compare episode counts.
+    pub fn episode_tolerance(&self) -> EpisodeTolerance {
+        self.episode_tolerance
+    }
+
+    /// The pipeline that sanitizes search and candidate titles.
+    pub fn normalizer(&self) -> &Normalizer {
+        &self.normalizer
+    }
+
+    /// How fuzzy matches are weighted by title field.
+    pub fn title_weights(&self) -> TitleWeights {
+        self.title_weights
+    }
+
+    /// How title pairs naming different seasons are treated.
+    pub fn season_mismatch(&self) -> SeasonMismatch {
+        self.season_mismatch
+    }
+
+    /// How candidates of another format are treated.
+    pub fn format_mismatch(&self) -> FormatMismatch {
+        self.format_mismatch
+    }
+
+    /// How candidates are scored.
+    pub fn scoring(&self) -> ScoringMode {
+        self.scoring
+    }
+
+    /// The metric a fuzzy tier scores title pairs with. Composite scoring
+    /// uses the metric set for [`MatchMethod::Composite`].
+    pub fn similarity(&self, method: MatchMethod) -> &dyn Similarity {
+        self.tier_similarities
+            .iter()
+            .find(|(m, _)| *m == method)
+            .map_or(self.similarity.as_ref(), |(_, metric)| metric.as_ref())
+    }
+}
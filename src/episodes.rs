@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+/// How strictly the episode-aware tiers compare episode counts.
+///
+/// The default requires both counts to be known and equal.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct EpisodeTolerance {
+    /// Episodes the counts may differ by.
+    pub absolute: u32,
+    /// Fraction of the larger count the counts may differ by.
+    pub relative: f64,
+    /// Treat a candidate count of `None` or 0 as unknown and let it match.
+    pub unknown_matches: bool,
+    /// Treat the searched count as a lower bound, for series still airing.
+    pub search_is_lower_bound: bool,
+}
+
+/// How a candidate's episode count compared to the searched count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EpisodeMatch {
+    Exact,
+    /// Off by the given number of episodes, within the configured tolerance
+    Within(u32),
+    /// The candidate has more episodes and the search is a lower bound
+    Ongoing,
+    /// The candidate's count is unknown
+    Unknown,
+}
+
+impl EpisodeTolerance {
+    /// Compares episode counts, returning `None` on a mismatch. A search
+    /// without a known count never matches.
+    pub(crate) fn compare(&self, search: Option<i32>, candidate: Option<i32>) -> Option<EpisodeMatch> {
+        let search = search.filter(|&episodes| episodes > 0 || !self.unknown_matches)?;
+
+        let candidate = match candidate {
+            Some(episodes) if episodes > 0 || !self.unknown_matches => episodes,
+            _ if self.unknown_matches => return Some(EpisodeMatch::Unknown),
+            _ => return None,
+        };
+
+        let gap = search.abs_diff(candidate);
+        if gap == 0 {
+            return Some(EpisodeMatch::Exact);
+        }
+
+        if self.search_is_lower_bound && candidate > search {
+            return Some(EpisodeMatch::Ongoing);
+        }
+
+        let allowed = (self.relative * search.max(candidate).max(0) as f64).floor() as u32;
+        (gap <= self.absolute.max(allowed)).then_some(EpisodeMatch::Within(gap))
+    }
+}
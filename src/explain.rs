@@ -0,0 +1,307 @@
+use serde::Serialize;
+
+use crate::composite::{ScoreBreakdown, ScoringMode};
+use crate::config::MatcherConfig;
+use crate::format::{self, FormatMismatch, MediaFormat};
+use crate::normalize::NormalizedTitles;
+use crate::r#match::{
+    Candidates, ExpectAnime, Ineligible, LazyCandidates, MatchMethod, PreparedSearch, TitleField, eligibility,
+    fuzzy, match_tier,
+};
+use crate::season::SeasonInfo;
+
+/// A structured trace of how a search was matched, for debugging.
+#[derive(Debug, Clone, Serialize)]
+pub struct Explanation {
+    pub search: SearchTrace,
+    /// Tiers in the order they were tried, up to the one that matched
+    pub tiers: Vec<TierTrace>,
+    pub winner: Option<Winner>,
+}
+
+/// The search titles and what they normalized to.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SearchTrace {
+    pub fields: Vec<TitleField>,
+    pub titles: Vec<String>,
+    pub normalized_titles: Vec<String>,
+    /// The season, part and cour read from each title
+    pub seasons: Vec<SeasonInfo>,
+    pub year: Option<i32>,
+    pub episodes: Option<i32>,
+    pub format: Option<MediaFormat>,
+    /// The titles written in kana, transliterated to romaji, as
+    /// [`MatchMethod::Transliterated`] compares them
+    pub transliterated: Option<Box<SearchTrace>>,
+}
+
+/// Every candidate a tier looked at.
+#[derive(Debug, Clone, Serialize)]
+pub struct TierTrace {
+    pub method: MatchMethod,
+    pub threshold: f64,
+    pub candidates: Vec<CandidateTrace>,
+    /// Index of the candidate that won this tier
+    pub best: Option<usize>,
+}
+
+/// How one candidate fared within a tier.
+#[derive(Debug, Clone, Serialize)]
+pub struct CandidateTrace {
+    /// Position of the candidate in the searched slice
+    pub index: usize,
+    pub id: Option<serde_json::Value>,
+    pub year: Option<i32>,
+    pub episodes: Option<i32>,
+    /// Set when the tier's year, episode or format filter excluded the candidate
+    pub ineligible: Option<Ineligible>,
+    pub pairs: Vec<PairTrace>,
+    /// The candidate's score in this tier, when it was accepted
+    pub similarity: Option<f64>,
+    /// Whether the candidate's format is the searched one, when both are known
+    pub format_match: Option<bool>,
+    pub breakdown: Option<ScoreBreakdown>,
+}
+
+/// One search title compared against one candidate title.
+#[derive(Debug, Clone, Serialize)]
+pub struct PairTrace {
+    pub search_field: TitleField,
+    pub search_title: String,
+    pub candidate_field: TitleField,
+    pub candidate_title: String,
+    /// Both titles after normalization, for tiers that compare normalized titles
+    pub normalized: Option<(String, String)>,
+    /// Adjusted for a season conflict when [`SeasonMismatch::Penalty`](crate::SeasonMismatch::Penalty)
+    /// applies
+    pub score: f64,
+    /// The titles name different seasons, parts or cours
+    pub season_conflict: bool,
+}
+
+/// The match the cascade settled on.
+#[derive(Debug, Clone, Serialize)]
+pub struct Winner {
+    pub method: MatchMethod,
+    pub index: usize,
+    pub similarity: f64,
+}
+
+/// Runs a search like [`find_best_match_with`](crate::find_best_match_with)
+/// and records every tier, candidate, title pair and score along the way.
+pub fn explain<T>(
+    search: &ExpectAnime,
+    results: &[T],
+    get_anime_data: impl Fn(&T) -> &ExpectAnime,
+    config: &MatcherConfig,
+) -> Explanation {
+    let prepared = PreparedSearch::new(search, config.normalizer());
+    let search_trace = match &prepared {
+        Some(prepared) => trace_search(prepared),
+        None => SearchTrace {
+            year: search.year,
+            episodes: search.episodes,
+            format: search.format,
+            ..SearchTrace::default()
+        },
+    };
+
+    let mut explanation = Explanation {
+        search: search_trace,
+        tiers: Vec::new(),
+        winner: None,
+    };
+
+    let Some(prepared) = prepared else {
+        return explanation;
+    };
+    let candidates = LazyCandidates::new(results, get_anime_data, config.normalizer());
+
+    let methods = match config.scoring() {
+        ScoringMode::Cascade => config.tiers().to_vec(),
+        ScoringMode::Composite(_) => vec![MatchMethod::Composite],
+    };
+
+    for method in methods {
+        let tier = trace_tier(method, config, &prepared, &candidates);
+
+        if let Some(best) = tier.best {
+            explanation.winner = Some(Winner {
+                method,
+                index: best,
+                similarity: tier.candidates[best].similarity.unwrap_or_default(),
+            });
+        }
+
+        explanation.tiers.push(tier);
+
+        if explanation.winner.is_some() {
+            break;
+        }
+    }
+
+    explanation
+}
+
+fn trace_search(search: &PreparedSearch) -> SearchTrace {
+    SearchTrace {
+        fields: search.fields.clone(),
+        titles: search.titles.clone(),
+        normalized_titles: search.normalized_titles.clone(),
+        seasons: search.seasons.clone(),
+        year: search.year,
+        episodes: search.episodes,
+        format: search.format,
+        transliterated: search.transliterated.as_deref().map(|search| Box::new(trace_search(search))),
+    }
+}
+
+fn trace_tier(
+    method: MatchMethod,
+    config: &MatcherConfig,
+    search: &PreparedSearch,
+    candidates: &impl Candidates,
+) -> TierTrace {
+    let threshold = match config.scoring() {
+        ScoringMode::Composite(scoring) => scoring.threshold,
+        ScoringMode::Cascade => config.threshold(method),
+    };
+
+    let mut tier = TierTrace {
+        method,
+        threshold,
+        candidates: Vec::with_capacity(candidates.len()),
+        best: None,
+    };
+
+    for index in 0..candidates.len() {
+        let candidate_data = candidates.anime(index);
+        let mut trace = CandidateTrace {
+            index,
+            id: candidate_data.id.clone(),
+            year: candidate_data.year,
+            episodes: candidate_data.episodes,
+            ineligible: None,
+            pairs: Vec::new(),
+            similarity: None,
+            format_match: format::compare(search.format, candidate_data.format),
+            breakdown: None,
+        };
+
+        let composite = matches!(config.scoring(), ScoringMode::Composite(_));
+        if composite && config.format_mismatch() == FormatMismatch::Reject && trace.format_match == Some(false) {
+            trace.ineligible = Some(Ineligible::Format);
+        } else if let ScoringMode::Composite(scoring) = config.scoring() {
+            let candidate_titles = candidates.titles(index);
+            trace.pairs = trace_pairs(method, config, search, candidate_titles);
+
+            let metric = config.similarity(method);
+            if let Some(title_match) =
+                fuzzy(search, candidate_titles, f64::NEG_INFINITY, metric, config)
+            {
+                let breakdown = scoring.score(
+                    title_match.similarity,
+                    (search.year, search.episodes, search.format),
+                    (candidate_data.year, candidate_data.episodes, candidate_data.format),
+                );
+                trace.similarity = (breakdown.composite >= scoring.threshold).then_some(breakdown.composite);
+                trace.breakdown = Some(breakdown);
+            }
+        } else {
+            match eligibility(method, config, search, candidate_data) {
+                Err(reason) => trace.ineligible = Some(reason),
+                Ok(_) => {
+                    let candidate_titles = candidates.titles(index);
+                    trace.pairs = trace_pairs(method, config, search, candidate_titles);
+                    trace.similarity =
+                        match_tier(method, config, search, candidate_data, candidate_titles).map(|m| m.similarity);
+                }
+            }
+        }
+
+        // The first of the most similar candidates wins, as in the cascade,
+        // where candidates of the searched format also win over the others
+        let cascade = config.scoring() == ScoringMode::Cascade;
+        let rank = |trace: &CandidateTrace| {
+            let preferred = !cascade || trace.format_match != Some(false);
+            (preferred, trace.similarity.unwrap_or_default())
+        };
+
+        if trace.similarity.is_some() && tier.best.is_none_or(|best| rank(&trace) > rank(&tier.candidates[best])) {
+            tier.best = Some(index);
+        }
+
+        tier.candidates.push(trace);
+    }
+
+    tier
+}
+
+/// Scores every search and candidate title pair the way the tier compares them
+fn trace_pairs(
+    method: MatchMethod,
+    config: &MatcherConfig,
+    search: &PreparedSearch,
+    candidate_titles: &NormalizedTitles,
+) -> Vec<PairTrace> {
+    let raw = matches!(
+        method,
+        MatchMethod::ExactYearEpisodeRaw | MatchMethod::ExactYearRaw | MatchMethod::Exact
+    );
+    let normalized = matches!(
+        method,
+        MatchMethod::ExactYearEpisodeNormalized | MatchMethod::ExactYearNormalized | MatchMethod::ExactNormalized
+    );
+    let metric = config.similarity(method);
+    let weights = config.title_weights();
+    let mut pairs = Vec::new();
+
+    let Some(search) = search.for_method(method) else {
+        return pairs;
+    };
+
+    let search_titles = search.fields.iter().zip(&search.titles).zip(&search.normalized_titles).zip(&search.seasons);
+
+    for (((&search_field, search_title), normalized_search_title), search_season) in search_titles {
+        for ((candidate_field, candidate_title, normalized_candidate_title), candidate_season) in
+            candidate_titles.entries().zip(&candidate_titles.seasons)
+        {
+            let season_conflict = search_season.conflicts_with(candidate_season);
+
+            if raw {
+                pairs.push(PairTrace {
+                    search_field,
+                    search_title: search_title.clone(),
+                    candidate_field,
+                    candidate_title: candidate_title.clone(),
+                    normalized: None,
+                    score: if search_title == candidate_title { 1.0 } else { 0.0 },
+                    season_conflict,
+                });
+                continue;
+            }
+
+            let score = if normalized {
+                if normalized_search_title == normalized_candidate_title { 1.0 } else { 0.0 }
+            } else if let Some(weight) = weights.pair(search_field, candidate_field) {
+                (metric.similarity(normalized_search_title, normalized_candidate_title) * weight).min(1.0)
+            } else {
+                // Not compared across languages
+                continue;
+            };
+            let score = config.season_mismatch().apply(search_season, candidate_season, score).unwrap_or(score);
+
+            pairs.push(PairTrace {
+                search_field,
+                search_title: search_title.clone(),
+                candidate_field,
+                candidate_title: candidate_title.clone(),
+                normalized: Some((normalized_search_title.clone(), normalized_candidate_title.clone())),
+                score,
+                season_conflict,
+            });
+        }
+    }
+
+    pairs
+}
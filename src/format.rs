@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+/// The kind of release an anime entry is, named as in AniList.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MediaFormat {
+    Tv,
+    TvShort,
+    Movie,
+    Special,
+    Ova,
+    Ona,
+    Music,
+}
+
+/// What the matcher does with a candidate whose format differs from the
+/// searched one. Nothing happens when either format is unknown.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum FormatMismatch {
+    /// Keep the candidate, but let any candidate of the searched format win
+    /// its tier over it.
+    #[default]
+    Prefer,
+    /// Subtract the given amount from the candidate's similarity.
+    Penalty(f64),
+    /// Never match the candidate.
+    Reject,
+}
+
+/// Whether two formats agree, or `None` when either is unknown
+pub(crate) fn compare(search: Option<MediaFormat>, candidate: Option<MediaFormat>) -> Option<bool> {
+    Some(search? == candidate?)
+}
@@ -0,0 +1,249 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::config::MatcherConfig;
+use crate::normalize::{NormalizedCatalogue, NormalizedTitles};
+use crate::prune::{NgramIndex, Pruning};
+use crate::r#match::{
+    Candidates, ExpectAnime, MatchMethod, MatchResult, PreparedSearch, best_match, ranked_matches, uses_year,
+};
+
+/// A catalogue prepared for many searches: titles are normalized once, exact
+/// and normalized titles are hashed, and candidates are bucketed by year.
+///
+/// Answers the same queries as [`find_best_match_with`](crate::find_best_match_with)
+/// and [`find_matches_with`](crate::find_matches_with) with identical results,
+/// but the exact tiers only look at candidates sharing a title with the search
+/// and the year tiers only at candidates within the year tolerance.
+///
+/// With [`MatchIndex::with_pruning`], the fuzzy tiers additionally score only
+/// a shortlist of candidates retrieved by shared n-grams, which is faster on
+/// large catalogues but no longer guaranteed to find the same match.
+#[derive(Debug, Clone)]
+pub struct MatchIndex<'a, T> {
+    catalogue: NormalizedCatalogue<'a, T>,
+    raw: HashMap<String, Vec<usize>>,
+    normalized: HashMap<String, Vec<usize>>,
+    years: BTreeMap<i32, Vec<usize>>,
+    ngrams: Option<NgramIndex>,
+}
+
+impl<'a, T> MatchIndex<'a, T> {
+    /// Indexes a normalized catalogue. Searches are sanitized with the
+    /// catalogue's normalizer.
+    pub fn new(catalogue: NormalizedCatalogue<'a, T>) -> Self {
+        let mut raw: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut normalized: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut years: BTreeMap<i32, Vec<usize>> = BTreeMap::new();
+
+        for (index, (anime, titles)) in catalogue.entries.iter().enumerate() {
+            for (raw_title, normalized_title) in titles.pairs() {
+                insert(&mut raw, raw_title, index);
+                insert(&mut normalized, normalized_title, index);
+            }
+
+            if let Some(year) = anime.year {
+                years.entry(year).or_default().push(index);
+            }
+        }
+
+        Self {
+            catalogue,
+            raw,
+            normalized,
+            years,
+            ngrams: None,
+        }
+    }
+
+    /// Prunes the candidates of the fuzzy tiers down to a shortlist.
+    pub fn with_pruning(mut self, pruning: Pruning) -> Self {
+        self.ngrams = Some(NgramIndex::new(pruning, self.catalogue.entries.iter().map(|(_, titles)| titles)));
+        self
+    }
+
+    pub fn pruning(&self) -> Option<Pruning> {
+        self.ngrams.as_ref().map(NgramIndex::pruning)
+    }
+
+    pub fn len(&self) -> usize {
+        self.catalogue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.catalogue.is_empty()
+    }
+
+    /// The titles of the candidate at `index`.
+    pub fn titles(&self, index: usize) -> Option<&NormalizedTitles> {
+        self.catalogue.titles(index)
+    }
+
+    /// Positions of the candidates whose year is within `tolerance` of `year`
+    fn within_years(&self, year: i32, tolerance: u32) -> Vec<usize> {
+        let range = year.saturating_sub_unsigned(tolerance)..=year.saturating_add_unsigned(tolerance);
+        let mut positions: Vec<usize> = self.years.range(range).flat_map(|(_, bucket)| bucket).copied().collect();
+        positions.sort_unstable();
+        positions
+    }
+
+    /// The share of `searches` whose brute-force best match is still found
+    /// with pruning, counting only searches that match at all. Always 1.0
+    /// without pruning.
+    pub fn recall(&self, searches: &[ExpectAnime], config: &MatcherConfig) -> f64 {
+        let mut matched = 0;
+        let mut recalled = 0;
+
+        for search in searches {
+            let Some(search) = PreparedSearch::new(search, &self.catalogue.normalizer) else {
+                continue;
+            };
+            let Some(expected) = best_match(&search, &BruteForce(self), config) else {
+                continue;
+            };
+
+            matched += 1;
+
+            if best_match(&search, self, config)
+                .is_some_and(|actual| actual.result == expected.result && actual.method == expected.method)
+            {
+                recalled += 1;
+            }
+        }
+
+        if matched == 0 {
+            return 1.0;
+        }
+
+        recalled as f64 / matched as f64
+    }
+
+    /// Positions of the only candidates that can match `method`, optionally
+    /// pruning the fuzzy tiers
+    fn shortlist(
+        &self,
+        method: MatchMethod,
+        config: &MatcherConfig,
+        search: &PreparedSearch,
+        prune: bool,
+    ) -> Option<Vec<usize>> {
+        let Some(search) = search.for_method(method) else {
+            return Some(Vec::new());
+        };
+
+        let titles = match method {
+            MatchMethod::ExactYearEpisodeRaw | MatchMethod::ExactYearRaw | MatchMethod::Exact => {
+                Some(lookup(&self.raw, &search.titles))
+            }
+            MatchMethod::ExactYearEpisodeNormalized | MatchMethod::ExactYearNormalized | MatchMethod::ExactNormalized => {
+                Some(lookup(&self.normalized, &search.normalized_titles))
+            }
+            _ => None,
+        };
+
+        let years = if uses_year(method) {
+            let Some(year) = search.year else {
+                return Some(Vec::new());
+            };
+            Some(self.within_years(year, config.year_tolerance()))
+        } else {
+            None
+        };
+
+        match (titles, years) {
+            (Some(titles), Some(years)) => {
+                Some(titles.into_iter().filter(|index| years.binary_search(index).is_ok()).collect())
+            }
+            (Some(titles), None) => Some(titles),
+            // A fuzzy tier
+            (None, years) => match &self.ngrams {
+                Some(ngrams) if prune => {
+                    let positions = years.unwrap_or_else(|| (0..self.len()).collect());
+                    Some(ngrams.prune(&search.normalized_titles, positions, self.len()))
+                }
+                _ => years,
+            },
+        }
+    }
+}
+
+impl<T: Clone> MatchIndex<'_, T> {
+    /// Same as [`find_best_match_with`](crate::find_best_match_with) over the whole catalogue.
+    pub fn find_best_match(&self, search: &ExpectAnime, config: &MatcherConfig) -> Option<MatchResult<T>> {
+        let search = PreparedSearch::new(search, &self.catalogue.normalizer)?;
+        best_match(&search, self, config).map(|m| m.map_result(|index| self.catalogue.results[index].clone()))
+    }
+
+    /// Same as [`find_matches_with`](crate::find_matches_with) over the whole catalogue.
+    pub fn find_matches(&self, search: &ExpectAnime, n: usize, config: &MatcherConfig) -> Vec<MatchResult<T>> {
+        let Some(search) = PreparedSearch::new(search, &self.catalogue.normalizer) else {
+            return Vec::new();
+        };
+
+        ranked_matches(&search, self, n, config)
+            .into_iter()
+            .map(|m| m.map_result(|index| self.catalogue.results[index].clone()))
+            .collect()
+    }
+}
+
+/// An index searched without pruning, for measuring recall
+struct BruteForce<'i, 'a, T>(&'i MatchIndex<'a, T>);
+
+impl<T> Candidates for BruteForce<'_, '_, T> {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn anime(&self, index: usize) -> &ExpectAnime {
+        self.0.catalogue.anime(index)
+    }
+
+    fn titles(&self, index: usize) -> &NormalizedTitles {
+        Candidates::titles(&self.0.catalogue, index)
+    }
+
+    fn shortlist(&self, method: MatchMethod, config: &MatcherConfig, search: &PreparedSearch) -> Option<Vec<usize>> {
+        self.0.shortlist(method, config, search, false)
+    }
+}
+
+impl<'a, T> From<NormalizedCatalogue<'a, T>> for MatchIndex<'a, T> {
+    fn from(catalogue: NormalizedCatalogue<'a, T>) -> Self {
+        Self::new(catalogue)
+    }
+}
+
+fn insert(map: &mut HashMap<String, Vec<usize>>, title: &str, index: usize) {
+    let positions = map.entry(title.to_owned()).or_default();
+
+    // A candidate may list the same title in several fields
+    if positions.last() != Some(&index) {
+        positions.push(index);
+    }
+}
+
+/// Positions of the candidates listed under any of `titles`, in ascending order
+fn lookup(map: &HashMap<String, Vec<usize>>, titles: &[String]) -> Vec<usize> {
+    let mut positions: Vec<usize> = titles.iter().filter_map(|title| map.get(title)).flatten().copied().collect();
+    positions.sort_unstable();
+    positions.dedup();
+    positions
+}
+
+impl<T> Candidates for MatchIndex<'_, T> {
+    fn len(&self) -> usize {
+        self.catalogue.len()
+    }
+
+    fn anime(&self, index: usize) -> &ExpectAnime {
+        self.catalogue.anime(index)
+    }
+
+    fn titles(&self, index: usize) -> &NormalizedTitles {
+        Candidates::titles(&self.catalogue, index)
+    }
+
+    fn shortlist(&self, method: MatchMethod, config: &MatcherConfig, search: &PreparedSearch) -> Option<Vec<usize>> {
+        MatchIndex::shortlist(self, method, config, search, true)
+    }
+}
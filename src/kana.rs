@@ -0,0 +1,117 @@
+use crate::native::fold_native;
+
+/// Hepburn readings of the hiragana syllables
+#[rustfmt::skip]
+const SYLLABLES: &[(char, &str)] = &[
+    ('あ', "a"), ('い', "i"), ('う', "u"), ('え', "e"), ('お', "o"),
+    ('か', "ka"), ('き', "ki"), ('く', "ku"), ('け', "ke"), ('こ', "ko"),
+    ('が', "ga"), ('ぎ', "gi"), ('ぐ', "gu"), ('げ', "ge"), ('ご', "go"),
+    ('さ', "sa"), ('し', "shi"), ('す', "su"), ('せ', "se"), ('そ', "so"),
+    ('ざ', "za"), ('じ', "ji"), ('ず', "zu"), ('ぜ', "ze"), ('ぞ', "zo"),
+    ('た', "ta"), ('ち', "chi"), ('つ', "tsu"), ('て', "te"), ('と', "to"),
+    ('だ', "da"), ('ぢ', "ji"), ('づ', "zu"), ('で', "de"), ('ど', "do"),
+    ('な', "na"), ('に', "ni"), ('ぬ', "nu"), ('ね', "ne"), ('の', "no"),
+    ('は', "ha"), ('ひ', "hi"), ('ふ', "fu"), ('へ', "he"), ('ほ', "ho"),
+    ('ば', "ba"), ('び', "bi"), ('ぶ', "bu"), ('べ', "be"), ('ぼ', "bo"),
+    ('ぱ', "pa"), ('ぴ', "pi"), ('ぷ', "pu"), ('ぺ', "pe"), ('ぽ', "po"),
+    ('ま', "ma"), ('み', "mi"), ('む', "mu"), ('め', "me"), ('も', "mo"),
+    ('や', "ya"), ('ゆ', "yu"), ('よ', "yo"),
+    ('ら', "ra"), ('り', "ri"), ('る', "ru"), ('れ', "re"), ('ろ', "ro"),
+    ('わ', "wa"), ('ゐ', "i"), ('ゑ', "e"), ('を', "o"), ('ん', "n"),
+    ('ゔ', "vu"), ('ゕ', "ka"), ('ゖ', "ke"), ('ゎ', "wa"),
+];
+
+/// Small kana that change the vowel of the syllable before them
+#[rustfmt::skip]
+const SMALL_VOWELS: &[(char, &str)] = &[
+    ('ゃ', "ya"), ('ゅ', "yu"), ('ょ', "yo"),
+    ('ぁ', "a"), ('ぃ', "i"), ('ぅ', "u"), ('ぇ', "e"), ('ぉ', "o"),
+];
+
+/// Transliterates hiragana and katakana into Hepburn romaji, leaving every
+/// other character as it is, so `ナルト 疾風伝` becomes `naruto 疾風伝`.
+///
+/// The text is folded with [`fold_native`] first, so long vowel marks are
+/// dropped and iteration marks spelled out. Kanji have no reading without a
+/// dictionary and are kept, separated from the romaji by a space.
+pub fn kana_to_romaji(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    // Romaji of the current run of kana, one entry per syllable
+    let mut syllables: Vec<String> = Vec::new();
+    let mut geminate = false;
+
+    for c in fold_native(text).chars() {
+        if let Some(&(_, romaji)) = SYLLABLES.iter().find(|(kana, _)| *kana == c) {
+            let mut syllable = romaji.to_string();
+            if std::mem::take(&mut geminate) && syllable != "n" && !syllable.starts_with(is_vowel) {
+                syllable.insert(
+                    0,
+                    if syllable.starts_with("ch") {
+                        't'
+                    } else {
+                        syllable.as_bytes()[0] as char
+                    },
+                );
+            }
+            syllables.push(syllable);
+        } else if let Some(&(_, vowel)) = SMALL_VOWELS.iter().find(|(kana, _)| *kana == c) {
+            match syllables.last_mut() {
+                Some(previous) => combine(previous, vowel),
+                None => syllables.push(vowel.trim_start_matches('y').to_string()),
+            }
+        } else if c == 'っ' {
+            geminate = true;
+        } else {
+            flush(&mut out, &mut syllables, c);
+            geminate = false;
+            out.push(c);
+        }
+    }
+
+    flush(&mut out, &mut syllables, ' ');
+    out.trim_end().to_string()
+}
+
+/// Whether a text has any hiragana or katakana, full or half width
+pub(crate) fn contains_kana(text: &str) -> bool {
+    text.chars()
+        .any(|c| matches!(c, '\u{3041}'..='\u{30FF}' | '\u{FF66}'..='\u{FF9D}'))
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'i' | 'u' | 'e' | 'o')
+}
+
+/// Merges a small kana into the syllable before it, as in `きゃ` or `ふぁ`
+fn combine(previous: &mut String, vowel: &str) {
+    let Some(stem) = previous
+        .strip_suffix(is_vowel)
+        .filter(|stem| !stem.is_empty())
+    else {
+        // A small vowel after a bare vowel is only lengthening it
+        previous.push_str(vowel.trim_start_matches('y'));
+        return;
+    };
+
+    // Hepburn drops the y after sh, ch and j, as in `しゃ`
+    let vowel = match vowel.strip_prefix('y') {
+        Some(bare) if ["sh", "ch", "j"].iter().any(|s| stem.ends_with(s)) => bare,
+        _ => vowel,
+    };
+    *previous = format!("{stem}{vowel}");
+}
+
+/// Writes out the pending run of kana, spaced from the text around it
+fn flush(out: &mut String, syllables: &mut Vec<String>, next: char) {
+    if syllables.is_empty() {
+        return;
+    }
+
+    if out.chars().last().is_some_and(|c| !c.is_whitespace()) {
+        out.push(' ');
+    }
+    out.extend(syllables.drain(..));
+    if !next.is_whitespace() {
+        out.push(' ');
+    }
+}
@@ -0,0 +1,70 @@
+//! Anime metadata matching.
+//!
+//! The entry point is [`find_best_match`], which runs a search through a
+//! cascade of exact, normalized and fuzzy tiers against any slice of
+//! candidates, and [`find_matches`] returns a ranked shortlist from the same
+//! cascade. [`match_title`] is a shorthand for the common case of matching
+//! a bare title against a list of [`ExpectAnime`]. The `_with` variants take a
+//! [`MatcherConfig`] to adjust thresholds, the order of the tiers and the
+//! [`Similarity`] metric each fuzzy tier scores with, or to replace the
+//! cascade with weighted [`ScoringMode::Composite`] scoring.
+//!
+//! For many searches against one catalogue, [`Normalizer::normalize_catalogue`]
+//! sanitizes every title once and [`MatchIndex`] adds hashed title lookups and
+//! year buckets on top, both returning the same results as [`find_best_match`].
+//! [`match_batch`] runs many searches against an index, in parallel with the
+//! `rayon` feature.
+//!
+//! When a match looks wrong, [`explain`] replays the search and returns a
+//! serializable trace of every tier, candidate and title pair it scored.
+
+mod batch;
+mod composite;
+mod config;
+mod episodes;
+mod explain;
+mod format;
+mod index;
+mod kana;
+mod r#match;
+mod native;
+mod normalize;
+mod prune;
+mod release;
+mod romaji;
+mod rules;
+mod season;
+mod similarity;
+mod weights;
+
+pub use batch::match_batch;
+pub use composite::{CompositeScoring, ScoreBreakdown, ScoringMode};
+pub use config::MatcherConfig;
+pub use episodes::{EpisodeMatch, EpisodeTolerance};
+pub use explain::{CandidateTrace, Explanation, PairTrace, SearchTrace, TierTrace, Winner, explain};
+pub use format::{FormatMismatch, MediaFormat};
+pub use index::MatchIndex;
+pub use kana::kana_to_romaji;
+pub use r#match::{
+    AnimeTitle, Confidence, ExpectAnime, Ineligible, MatchMethod, MatchResult, TitleField, YearMatch,
+    clean_title, find_best_match, find_best_match_with, find_matches, find_matches_with,
+    jaro_winkler_distance, jaro_winkler_graphemes, sanitize_title,
+};
+pub use native::fold_native;
+pub use normalize::{NormalizedCatalogue, NormalizedTitles, Normalizer};
+pub use prune::Pruning;
+pub use release::ParsedRelease;
+pub use romaji::canonicalize_romaji;
+pub use rules::{NormalizationRules, RuleError, RulesError};
+pub use season::{SeasonInfo, SeasonMismatch};
+pub use similarity::{
+    DamerauLevenshtein, JaroWinkler, Levenshtein, NgramCosine, NgramJaccard, Similarity,
+    TokenSetRatio, TokenSortRatio,
+};
+pub use weights::TitleWeights;
+
+/// Matches a bare title against a list of candidates, ignoring year and episodes.
+pub fn match_title(title: &str, candidates: &[ExpectAnime]) -> Option<MatchResult<ExpectAnime>> {
+    let search = ExpectAnime::from_string_title(title.to_string(), None, None);
+    find_best_match(&search, candidates, |anime| anime)
+}
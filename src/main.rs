@@ -1,6 +1,4 @@
-use crate::r#match::{find_best_match, AnimeTitle, ExpectAnime};
-
-mod r#match;
+use am_algorithm::{AnimeTitle, ExpectAnime, find_best_match};
 
 fn main() {
     let mock_anime_list = vec![
@@ -11,9 +9,11 @@ fn main() {
                 romaji: Some("Shingeki no Kyojin".into()),
                 native: Some("進撃の巨人".into()),
                 user_preferred: Some("Shingeki no Kyojin".into()),
+                synonyms: vec!["AOT".into(), "SnK".into()],
             }),
             year: Some(2013),
             episodes: Some(25),
+            format: None,
         },
         ExpectAnime {
             id: Some(serde_json::json!(2)),
@@ -22,9 +22,11 @@ fn main() {
                 romaji: Some("Kimetsu no Yaiba".into()),
                 native: Some("鬼滅の刃".into()),
                 user_preferred: Some("Kimetsu no Yaiba".into()),
+                synonyms: Vec::new(),
             }),
             year: Some(2019),
             episodes: Some(26),
+            format: None,
         },
         ExpectAnime {
             id: Some(serde_json::json!(3)),
@@ -33,9 +35,11 @@ fn main() {
                 romaji: Some("Boku no Hero Academia 2nd Season".into()),
                 native: Some("僕のヒーローアカデミア 第2期".into()),
                 user_preferred: Some("Boku no Hero Academia 2nd Season".into()),
+                synonyms: Vec::new(),
             }),
             year: Some(2017),
             episodes: Some(25),
+            format: None,
         },
         ExpectAnime {
             id: Some(serde_json::json!(4)),
@@ -44,9 +48,11 @@ fn main() {
                 romaji: Some("Steins;Gate".into()),
                 native: Some("シュタインズ・ゲート".into()),
                 user_preferred: Some("Steins Gate".into()),
+                synonyms: Vec::new(),
             }),
             year: Some(2011),
             episodes: Some(24),
+            format: None,
         },
         ExpectAnime {
             id: Some(serde_json::json!(5)),
@@ -54,10 +60,12 @@ fn main() {
                 english: Some("Fullmetal Alchemist: Brotherhood".into()),
                 romaji: Some("Hagane no Renkinjutsushi: Fullmetal Alchemist".into()),
                 native: Some("鋼の錬金術師 FULLMETAL ALCHEMIST".into()),
-                user_preferred: Some("FMA Brotherhood".into()),
+                user_preferred: Some("Hagane no Renkinjutsushi: Fullmetal Alchemist".into()),
+                synonyms: vec!["FMA Brotherhood".into(), "FMAB".into()],
             }),
             year: Some(2009),
             episodes: Some(64),
+            format: None,
         },
         ExpectAnime {
             id: Some(serde_json::json!(6)),
@@ -66,9 +74,11 @@ fn main() {
                 romaji: Some("Naruto: Shippuuden".into()),
                 native: Some("ナルト 疾風伝".into()),
                 user_preferred: Some("Naruto Shippuuden".into()),
+                synonyms: Vec::new(),
             }),
             year: Some(2007),
             episodes: Some(500),
+            format: None,
         },
         ExpectAnime {
             id: Some(serde_json::json!(7)),
@@ -77,9 +87,11 @@ fn main() {
                 romaji: Some("One Piece".into()),
                 native: Some("ワンピース".into()),
                 user_preferred: Some("One Piece".into()),
+                synonyms: Vec::new(),
             }),
             year: Some(1999),
             episodes: Some(1000),
+            format: None,
         },
         ExpectAnime {
             id: Some(serde_json::json!(8)),
@@ -87,20 +99,25 @@ fn main() {
                 english: Some("Bleach: Thousand-Year Blood War".into()),
                 romaji: Some("Bleach: Sennen Kessen-hen".into()),
                 native: Some("BLEACH 千年血戦篇".into()),
-                user_preferred: Some("Bleach TYBW".into()),
+                user_preferred: Some("Bleach: Sennen Kessen-hen".into()),
+                synonyms: vec!["Bleach TYBW".into()],
             }),
             year: Some(2022),
             episodes: Some(13),
+            format: None,
         },
     ];
 
     let search = ExpectAnime::from_string_title("Attack on Titan".to_string(), Some(2013), Some(25));
 
-let result = find_best_match(&search, &mock_anime_list, |anime| anime);
-
-match result {
-    Some(m) => println!("🎯 Found match: {:?} via {:?}", m.title.or(m.normalized), m.method),
-    None => println!("No match found..."),
-}
+    let result = find_best_match(&search, &mock_anime_list, |anime| anime);
 
+    match result {
+        Some(m) => println!(
+            "🎯 Found match: {} via {:?}",
+            m.matched_via().unwrap_or_default(),
+            m.method
+        ),
+        None => println!("No match found..."),
+    }
 }
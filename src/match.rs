@@ -1,12 +1,50 @@
+use std::cell::OnceCell;
+
 use serde::{Deserialize, Serialize};
 use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use crate::composite::{CompositeScoring, ScoreBreakdown, ScoringMode};
+use crate::config::MatcherConfig;
+use crate::episodes::EpisodeMatch;
+use crate::format::{self, FormatMismatch, MediaFormat};
+use crate::kana::{contains_kana, kana_to_romaji};
+use crate::normalize::{NormalizedCatalogue, NormalizedTitles, Normalizer};
+use crate::season::SeasonInfo;
+use crate::similarity::Similarity;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AnimeTitle {
     pub english: Option<String>,
     pub romaji: Option<String>,
     pub native: Option<String>,
     pub user_preferred: Option<String>,
+    /// Abbreviations and alternative names, such as "AOT"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub synonyms: Vec<String>,
+}
+
+/// The field of an [`AnimeTitle`] a title was taken from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TitleField {
+    UserPreferred,
+    English,
+    Romaji,
+    Native,
+    Synonym,
+}
+
+impl std::fmt::Display for TitleField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            TitleField::UserPreferred => "User preferred",
+            TitleField::English => "English",
+            TitleField::Romaji => "Romaji",
+            TitleField::Native => "Native",
+            TitleField::Synonym => "Synonym",
+        })
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,6 +53,8 @@ pub struct ExpectAnime {
     pub title: Option<AnimeTitle>,
     pub year: Option<i32>,
     pub episodes: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<MediaFormat>,
 }
 
 impl ExpectAnime {
@@ -26,14 +66,23 @@ impl ExpectAnime {
                 romaji: None,
                 native: None,
                 user_preferred: None,
+                synonyms: Vec::new(),
             }),
             year,
             episodes,
+            format: None,
         }
     }
 }
 
-#[derive(Debug, Clone)]
+/// The tier of the cascade that produced a match.
+///
+/// Methods order by tier strength, so a stronger tier compares greater.
+/// [`MatchMethod::Transliterated`] is an optional tier left out of
+/// [`MatchMethod::ALL`] that sorts just below [`MatchMethod::NullMethod`], and
+/// [`MatchMethod::Composite`] is not a tier and sorts last.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum MatchMethod {
     ExactYearEpisodeRaw,
     ExactYearEpisodeNormalized,
@@ -45,54 +94,195 @@ pub enum MatchMethod {
     Loose,
     LastResort,
     NullMethod,
+    /// Native search titles transliterated to romaji and compared with fuzzy
+    /// similarity, enabled with [`MatcherConfig::with_tier`]
+    Transliterated,
+    /// Weighted title, year and episode score, see [`ScoringMode::Composite`]
+    Composite,
 }
 
-#[derive(Debug, Clone)]
+impl Ord for MatchMethod {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.strength().cmp(&other.strength())
+    }
+}
+
+impl PartialOrd for MatchMethod {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl MatchMethod {
+    /// Every tier of the matching cascade, strongest first.
+    pub const ALL: [MatchMethod; 10] = [
+        MatchMethod::ExactYearEpisodeRaw,
+        MatchMethod::ExactYearEpisodeNormalized,
+        MatchMethod::ExactYearRaw,
+        MatchMethod::ExactYearNormalized,
+        MatchMethod::Exact,
+        MatchMethod::ExactNormalized,
+        MatchMethod::LooseYear,
+        MatchMethod::Loose,
+        MatchMethod::LastResort,
+        MatchMethod::NullMethod,
+    ];
+
+    /// Whether the method only accepts identical titles, raw or normalized.
+    pub fn is_exact(self) -> bool {
+        matches!(
+            self,
+            MatchMethod::ExactYearEpisodeRaw
+                | MatchMethod::ExactYearEpisodeNormalized
+                | MatchMethod::ExactYearRaw
+                | MatchMethod::ExactYearNormalized
+                | MatchMethod::Exact
+                | MatchMethod::ExactNormalized
+        )
+    }
+
+    fn strength(self) -> u8 {
+        match self {
+            MatchMethod::ExactYearEpisodeRaw => 11,
+            MatchMethod::ExactYearEpisodeNormalized => 10,
+            MatchMethod::ExactYearRaw => 9,
+            MatchMethod::ExactYearNormalized => 8,
+            MatchMethod::Exact => 7,
+            MatchMethod::ExactNormalized => 6,
+            MatchMethod::LooseYear => 5,
+            MatchMethod::Loose => 4,
+            MatchMethod::LastResort => 3,
+            MatchMethod::NullMethod => 2,
+            MatchMethod::Transliterated => 1,
+            MatchMethod::Composite => 0,
+        }
+    }
+}
+
+/// How a candidate's year compared to the searched year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum YearMatch {
+    Exact,
+    /// Off by the given number of years, within the configured tolerance
+    Within(u32),
+}
+
+/// A coarse reading of how trustworthy a match is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Confidence {
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MatchResult<T> {
     pub similarity: f64,
     pub method: MatchMethod,
     pub result: T,
     pub title: Option<String>,
     pub normalized: Option<String>,
+    /// The candidate title that produced the match, as written in the catalogue
+    pub candidate_title: Option<String>,
+    /// Which of the candidate's title fields `candidate_title` came from
+    pub matched_field: Option<TitleField>,
     pub year: Option<i32>,
     pub episodes: Option<i32>,
+    /// How closely the candidate's year matched, for tiers that compare years
+    pub year_match: Option<YearMatch>,
+    /// How closely the candidate's episode count matched, for tiers that compare episodes
+    pub episode_match: Option<EpisodeMatch>,
+    /// Whether the candidate's format is the searched one, when both are known
+    pub format_match: Option<bool>,
+    /// Per-component scores when the match came from composite scoring
+    pub breakdown: Option<ScoreBreakdown>,
+}
+
+impl<T> MatchResult<T> {
+    /// Exact tiers are high confidence unless the year only matched within
+    /// tolerance, the loose tiers are medium and the last-resort and
+    /// transliterated tiers low.
+    /// Composite matches are graded by their score.
+    pub fn confidence(&self) -> Confidence {
+        match self.method {
+            method if method.is_exact() => match self.year_match {
+                Some(YearMatch::Within(_)) => Confidence::Medium,
+                _ => Confidence::High,
+            },
+            MatchMethod::LooseYear | MatchMethod::Loose => Confidence::Medium,
+            MatchMethod::Composite if self.similarity >= 0.9 => Confidence::High,
+            MatchMethod::Composite if self.similarity >= 0.75 => Confidence::Medium,
+            _ => Confidence::Low,
+        }
+    }
+
+    /// The candidate field and title that produced the match, for display,
+    /// e.g. `Romaji: Shingeki no Kyojin`.
+    pub fn matched_via(&self) -> Option<String> {
+        let field = self.matched_field?;
+        let title = self.candidate_title.as_ref()?;
+        Some(format!("{field}: {title}"))
+    }
 }
 
 /// Calculates the Jaro-Winkler distance between two strings.
 /// Returns a value between 0 and 1, where 1 means the strings are identical
 /// and 0 means they are completely different.
+///
+/// Strings are compared by Unicode scalar value, so multi-byte scripts are
+/// weighted the same as ASCII.
 pub fn jaro_winkler_distance(s1: &str, s2: &str, p: Option<f64>) -> f64 {
-    let p = p.unwrap_or(0.1);
-    
     if s1 == s2 {
         return 1.0;
     }
-    
+
+    let s1_chars: Vec<char> = s1.chars().collect();
+    let s2_chars: Vec<char> = s2.chars().collect();
+
+    jaro_winkler(&s1_chars, &s2_chars, p)
+}
+
+/// Calculates the Jaro-Winkler distance between two strings by grapheme
+/// cluster, so a base character and its combining marks count as one unit.
+pub fn jaro_winkler_graphemes(s1: &str, s2: &str, p: Option<f64>) -> f64 {
+    if s1 == s2 {
+        return 1.0;
+    }
+
+    let s1_graphemes: Vec<&str> = s1.graphemes(true).collect();
+    let s2_graphemes: Vec<&str> = s2.graphemes(true).collect();
+
+    jaro_winkler(&s1_graphemes, &s2_graphemes, p)
+}
+
+/// Jaro-Winkler over any sequence of comparable units
+fn jaro_winkler<U: PartialEq>(s1: &[U], s2: &[U], p: Option<f64>) -> f64 {
+    let p = p.unwrap_or(0.1);
+
     if s1.is_empty() && s2.is_empty() {
         return 1.0;
     }
-    
+
     if s1.is_empty() || s2.is_empty() {
         return 0.0;
     }
-    
-    let scaling_factor = p.max(0.0).min(0.25);
+
+    let scaling_factor = p.clamp(0.0, 0.25);
     let match_distance = (s1.len().max(s2.len()) / 2).saturating_sub(1);
-    
+
     let mut s1_matches = vec![false; s1.len()];
     let mut s2_matches = vec![false; s2.len()];
     let mut matching_chars = 0;
-    
-    let s1_chars: Vec<char> = s1.chars().collect();
-    let s2_chars: Vec<char> = s2.chars().collect();
-    
+
     // Find matches
-    for (i, &ch1) in s1_chars.iter().enumerate() {
+    for (i, ch1) in s1.iter().enumerate() {
         let start = i.saturating_sub(match_distance);
-        let end = (i + match_distance + 1).min(s2_chars.len());
-        
+        let end = (i + match_distance + 1).min(s2.len());
+
         for j in start..end {
-            if !s2_matches[j] && ch1 == s2_chars[j] {
+            if !s2_matches[j] && *ch1 == s2[j] {
                 s1_matches[i] = true;
                 s2_matches[j] = true;
                 matching_chars += 1;
@@ -100,48 +290,44 @@ pub fn jaro_winkler_distance(s1: &str, s2: &str, p: Option<f64>) -> f64 {
             }
         }
     }
-    
+
     if matching_chars == 0 {
         return 0.0;
     }
-    
+
     // Calculate transpositions
     let mut transpositions = 0;
     let mut k = 0;
-    
+
     for (i, &is_match) in s1_matches.iter().enumerate() {
         if is_match {
             while !s2_matches[k] {
                 k += 1;
             }
-            
-            if s1_chars[i] != s2_chars[k] {
+
+            if s1[i] != s2[k] {
                 transpositions += 1;
             }
-            
+
             k += 1;
         }
     }
-    
+
     transpositions /= 2;
-    
+
     let jaro_similarity = (matching_chars as f64 / s1.len() as f64
         + matching_chars as f64 / s2.len() as f64
         + (matching_chars - transpositions) as f64 / matching_chars as f64)
         / 3.0;
-    
+
     // Calculate common prefix length
-    let mut common_prefix_length = 0;
-    let max_prefix_length = 4.min(s1_chars.len()).min(s2_chars.len());
-    
-    for i in 0..max_prefix_length {
-        if s1_chars[i] == s2_chars[i] {
-            common_prefix_length += 1;
-        } else {
-            break;
-        }
-    }
-    
+    let common_prefix_length = s1
+        .iter()
+        .zip(s2)
+        .take(4)
+        .take_while(|(a, b)| a == b)
+        .count();
+
     jaro_similarity + common_prefix_length as f64 * scaling_factor * (1.0 - jaro_similarity)
 }
 
@@ -163,387 +349,670 @@ pub fn clean_title(title: Option<&str>) -> Option<String> {
 }
 
 /// Sanitizes a title string by removing unnecessary words and characters for comparison.
+///
+/// Uses the default [`Normalizer`]; build one directly to sanitize many titles
+/// or to pre-normalize a catalogue.
 pub fn sanitize_title(title: Option<&str>) -> Option<String> {
-    title.and_then(|t| {
-        let mut sanitized = t.to_lowercase();
-        
-        // Replace chapters with chapter
-        sanitized = sanitized.replace("chapters", "chapter");
-        
-        // Remove specific words related to anime seasons or parts
-        let season_regex = regex::Regex::new(r"\b(season|cour|part|chapter|special)\b").unwrap();
-        sanitized = season_regex.replace_all(&sanitized, "").to_string();
-        
-        // Remove specific words related to anime seasons or parts with numbers
-        let numbered_season_regex = regex::Regex::new(r"(\d+)(?:th|rd|nd|st)?\s*(?:season|cour|part|chapter|special)\b").unwrap();
-        sanitized = numbered_season_regex.replace_all(&sanitized, " $1 ").to_string();
-        
-        // Remove non-alphanumeric characters
-        sanitized = sanitized.chars()
-            .map(|c| if c.is_alphanumeric() || c.is_whitespace() { c } else { ' ' })
-            .collect();
-        
-        // Replace specific words to ensure consistency
-        sanitized = sanitized.replace("yuu", "yu");
-        sanitized = sanitized.replace("ouh", "oh");
-        sanitized = sanitized.replace("yaa", "ya");
-        
-        // Remove specific words related to anime formats or additional information
-        let format_regex = regex::Regex::new(r"\b(?:uncut|uncensored|dub(?:bed)?|censored|sub(?:bed)?|the final chapters)\b|\([^)]*\)|\bBD\b|\(TV\)").unwrap();
-        sanitized = format_regex.replace_all(&sanitized, "").to_string();
-        
-        // Normalize the string to remove accents and other diacritical marks
-        sanitized = sanitized.nfd()
-            .filter(|c| !unicode_categories::UnicodeCategories::is_mark(*c))
-            .collect();
-        
-        clean_title(Some(&sanitized))
-    })
+    title.map(|t| Normalizer::shared().sanitize(t))
 }
 
-/// Gets all available titles from an anime title object
-fn get_all_titles(title: &Option<AnimeTitle>) -> Vec<String> {
+/// Gets all available titles from an anime title object, with the field each came from
+pub(crate) fn get_all_titles(title: &Option<AnimeTitle>) -> Vec<(TitleField, String)> {
     match title {
         Some(t) => {
             let mut titles = Vec::new();
             if let Some(ref pref) = t.user_preferred {
-                titles.push(pref.clone());
+                titles.push((TitleField::UserPreferred, pref.clone()));
             }
             if let Some(ref eng) = t.english {
-                titles.push(eng.clone());
+                titles.push((TitleField::English, eng.clone()));
             }
             if let Some(ref rom) = t.romaji {
-                titles.push(rom.clone());
+                titles.push((TitleField::Romaji, rom.clone()));
             }
             if let Some(ref nat) = t.native {
-                titles.push(nat.clone());
+                titles.push((TitleField::Native, nat.clone()));
             }
-            titles.into_iter().filter(|s| !s.is_empty()).collect()
+            for synonym in &t.synonyms {
+                titles.push((TitleField::Synonym, synonym.clone()));
+            }
+            titles.into_iter().filter(|(_, s)| !s.is_empty()).collect()
         }
         None => Vec::new(),
     }
 }
 
-/// Finds the best matching anime from a list of results based on the search criteria
-pub fn find_best_match<T: Clone>(
-    search: &ExpectAnime,
-    results: &[T],
-    get_anime_data: impl Fn(&T) -> &ExpectAnime,
-) -> Option<MatchResult<T>> {
-    if results.is_empty() {
-        return None;
+/// Search titles prepared once per query
+pub(crate) struct PreparedSearch {
+    pub(crate) fields: Vec<TitleField>,
+    pub(crate) titles: Vec<String>,
+    pub(crate) normalized_titles: Vec<String>,
+    pub(crate) seasons: Vec<SeasonInfo>,
+    pub(crate) year: Option<i32>,
+    pub(crate) episodes: Option<i32>,
+    pub(crate) format: Option<MediaFormat>,
+    /// The search titles written in kana, transliterated to romaji and
+    /// compared as romaji titles
+    pub(crate) transliterated: Option<Box<PreparedSearch>>,
+}
+
+impl PreparedSearch {
+    pub(crate) fn new(search: &ExpectAnime, normalizer: &Normalizer) -> Option<Self> {
+        let NormalizedTitles {
+            raw: titles,
+            normalized: normalized_titles,
+            fields,
+            seasons,
+        } = normalizer.normalize_titles(&search.title);
+
+        if titles.is_empty() {
+            return None;
+        }
+
+        let (kana_titles, kana_seasons): (Vec<_>, Vec<_>) =
+            titles.iter().zip(&seasons).filter(|(title, _)| contains_kana(title)).unzip();
+        let transliterated = (!kana_titles.is_empty()).then(|| {
+            let romaji: Vec<String> = kana_titles.into_iter().map(|title| kana_to_romaji(title)).collect();
+
+            Box::new(Self {
+                fields: vec![TitleField::Romaji; romaji.len()],
+                normalized_titles: romaji.iter().map(|title| normalizer.sanitize(title)).collect(),
+                titles: romaji,
+                seasons: kana_seasons.into_iter().copied().collect(),
+                year: search.year,
+                episodes: search.episodes,
+                format: search.format,
+                transliterated: None,
+            })
+        });
+
+        Some(Self {
+            fields,
+            titles,
+            normalized_titles,
+            seasons,
+            year: search.year,
+            episodes: search.episodes,
+            format: search.format,
+            transliterated,
+        })
     }
-    
-    let search_titles = get_all_titles(&search.title);
-    if search_titles.is_empty() {
-        return None;
+
+    /// The search as a tier compares it: transliterated for
+    /// [`MatchMethod::Transliterated`], or `None` when it has no kana titles
+    pub(crate) fn for_method(&self, method: MatchMethod) -> Option<&PreparedSearch> {
+        match method {
+            MatchMethod::Transliterated => self.transliterated.as_deref(),
+            _ => Some(self),
+        }
     }
-    
-    let search_year = search.year;
-    let search_episodes = search.episodes;
-    
-    // Normalize search titles
-    let normalized_search_titles: Vec<String> = search_titles
-        .iter()
-        .filter_map(|t| sanitize_title(Some(t)))
-        .collect();
-    
-    if normalized_search_titles.is_empty() {
-        return None;
+}
+
+/// Candidate metadata and titles, looked up by position
+pub(crate) trait Candidates {
+    fn len(&self) -> usize;
+
+    fn anime(&self, index: usize) -> &ExpectAnime;
+
+    fn titles(&self, index: usize) -> &NormalizedTitles;
+
+    /// Positions of the only candidates that can match `method`, in ascending
+    /// order, or `None` when every candidate has to be tried
+    fn shortlist(&self, _method: MatchMethod, _config: &MatcherConfig, _search: &PreparedSearch) -> Option<Vec<usize>> {
+        None
     }
-    
-    // 1. Exact Match with year and episodes
-    if let (Some(year), Some(episodes)) = (search_year, search_episodes) {
-        for candidate in results {
-            let candidate_data = get_anime_data(candidate);
-            let candidate_titles = get_all_titles(&candidate_data.title);
-            
-            if candidate_data.year == Some(year) && candidate_data.episodes == Some(episodes) {
-                for search_title in &search_titles {
-                    if candidate_titles.contains(search_title) {
-                        return Some(MatchResult {
-                            similarity: 1.0,
-                            method: MatchMethod::ExactYearEpisodeRaw,
-                            result: candidate.clone(),
-                            title: Some(search_title.clone()),
-                            normalized: None,
-                            year: Some(year),
-                            episodes: Some(episodes),
-                        });
-                    }
-                }
-            }
-        }
+}
+
+/// Iterates over a tier's shortlist, or over every candidate without one
+fn positions(shortlist: &Option<Vec<usize>>, len: usize) -> Box<dyn Iterator<Item = usize> + '_> {
+    match shortlist {
+        Some(shortlist) => Box::new(shortlist.iter().copied()),
+        None => Box::new(0..len),
     }
-    
-    // 2. Exact Match with normalized titles, year and episodes
-    if let (Some(year), Some(episodes)) = (search_year, search_episodes) {
-        for candidate in results {
-            let candidate_data = get_anime_data(candidate);
-            let candidate_titles = get_all_titles(&candidate_data.title);
-            let normalized_candidate_titles: Vec<String> = candidate_titles
-                .iter()
-                .filter_map(|t| sanitize_title(Some(t)))
-                .collect();
-            
-            if candidate_data.year == Some(year) && candidate_data.episodes == Some(episodes) {
-                for normalized_search_title in &normalized_search_titles {
-                    if normalized_candidate_titles.contains(normalized_search_title) {
-                        return Some(MatchResult {
-                            similarity: 1.0,
-                            method: MatchMethod::ExactYearEpisodeNormalized,
-                            result: candidate.clone(),
-                            title: None,
-                            normalized: Some(normalized_search_title.clone()),
-                            year: Some(year),
-                            episodes: Some(episodes),
-                        });
-                    }
-                }
-            }
+}
+
+/// Candidates from a plain slice, normalizing each one's titles on first use
+pub(crate) struct LazyCandidates<'a, T, F> {
+    results: &'a [T],
+    get_anime_data: F,
+    normalizer: &'a Normalizer,
+    titles: Vec<OnceCell<NormalizedTitles>>,
+}
+
+impl<'a, T, F: Fn(&T) -> &ExpectAnime> LazyCandidates<'a, T, F> {
+    pub(crate) fn new(results: &'a [T], get_anime_data: F, normalizer: &'a Normalizer) -> Self {
+        Self {
+            results,
+            get_anime_data,
+            normalizer,
+            titles: (0..results.len()).map(|_| OnceCell::new()).collect(),
         }
     }
-    
-    // 3. Exact Match with year
-    if let Some(year) = search_year {
-        for candidate in results {
-            let candidate_data = get_anime_data(candidate);
-            let candidate_titles = get_all_titles(&candidate_data.title);
-            
-            if candidate_data.year == Some(year) {
-                for search_title in &search_titles {
-                    if candidate_titles.contains(search_title) {
-                        return Some(MatchResult {
-                            similarity: 1.0,
-                            method: MatchMethod::ExactYearRaw,
-                            result: candidate.clone(),
-                            title: Some(search_title.clone()),
-                            normalized: None,
-                            year: Some(year),
-                            episodes: None,
-                        });
-                    }
-                }
-            }
+}
+
+impl<T, F: Fn(&T) -> &ExpectAnime> Candidates for LazyCandidates<'_, T, F> {
+    fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    fn anime(&self, index: usize) -> &ExpectAnime {
+        (self.get_anime_data)(&self.results[index])
+    }
+
+    fn titles(&self, index: usize) -> &NormalizedTitles {
+        self.titles[index].get_or_init(|| self.normalizer.normalize_titles(&self.anime(index).title))
+    }
+}
+
+impl<T> Candidates for NormalizedCatalogue<'_, T> {
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn anime(&self, index: usize) -> &ExpectAnime {
+        self.entries[index].0
+    }
+
+    fn titles(&self, index: usize) -> &NormalizedTitles {
+        &self.entries[index].1
+    }
+}
+
+/// The outcome of comparing one candidate within a single tier
+pub(crate) struct TierMatch {
+    pub(crate) similarity: f64,
+    title: Option<String>,
+    normalized: Option<String>,
+    candidate_title: String,
+    candidate_field: TitleField,
+    year_gap: Option<u32>,
+    episode_match: Option<EpisodeMatch>,
+    pub(crate) format_match: Option<bool>,
+}
+
+impl TierMatch {
+    /// Whether the candidate is not known to be of another format than the search
+    pub(crate) fn preferred(&self) -> bool {
+        self.format_match != Some(false)
+    }
+
+    fn into_result<T>(self, method: MatchMethod, search: &PreparedSearch, result: T) -> MatchResult<T> {
+        let (year, episodes) = match method {
+            MatchMethod::ExactYearEpisodeRaw | MatchMethod::ExactYearEpisodeNormalized => (search.year, search.episodes),
+            MatchMethod::ExactYearRaw | MatchMethod::ExactYearNormalized | MatchMethod::LooseYear => (search.year, None),
+            _ => (None, None),
+        };
+
+        MatchResult {
+            similarity: self.similarity,
+            method,
+            result,
+            title: self.title,
+            normalized: self.normalized,
+            candidate_title: Some(self.candidate_title),
+            matched_field: Some(self.candidate_field),
+            year,
+            episodes,
+            year_match: self.year_gap.map(|gap| if gap == 0 { YearMatch::Exact } else { YearMatch::Within(gap) }),
+            episode_match: self.episode_match,
+            format_match: self.format_match,
+            breakdown: None,
         }
     }
-    
-    // 4. Exact Match with normalized titles and year
-    if let Some(year) = search_year {
-        for candidate in results {
-            let candidate_data = get_anime_data(candidate);
-            let candidate_titles = get_all_titles(&candidate_data.title);
-            let normalized_candidate_titles: Vec<String> = candidate_titles
-                .iter()
-                .filter_map(|t| sanitize_title(Some(t)))
-                .collect();
-            
-            if candidate_data.year == Some(year) {
-                for normalized_search_title in &normalized_search_titles {
-                    if normalized_candidate_titles.contains(normalized_search_title) {
-                        return Some(MatchResult {
-                            similarity: 1.0,
-                            method: MatchMethod::ExactYearNormalized,
-                            result: candidate.clone(),
-                            title: None,
-                            normalized: Some(normalized_search_title.clone()),
-                            year: Some(year),
-                            episodes: None,
-                        });
-                    }
-                }
-            }
+}
+
+impl<T> MatchResult<T> {
+    pub(crate) fn map_result<U>(self, f: impl FnOnce(T) -> U) -> MatchResult<U> {
+        MatchResult {
+            similarity: self.similarity,
+            method: self.method,
+            result: f(self.result),
+            title: self.title,
+            normalized: self.normalized,
+            candidate_title: self.candidate_title,
+            matched_field: self.matched_field,
+            year: self.year,
+            episodes: self.episodes,
+            year_match: self.year_match,
+            episode_match: self.episode_match,
+            format_match: self.format_match,
+            breakdown: self.breakdown,
         }
     }
-    
-    // 5. Exact title match
-    for candidate in results {
-        let candidate_data = get_anime_data(candidate);
-        let candidate_titles = get_all_titles(&candidate_data.title);
-        
-        for search_title in &search_titles {
-            if candidate_titles.contains(search_title) {
-                return Some(MatchResult {
-                    similarity: 1.0,
-                    method: MatchMethod::Exact,
-                    result: candidate.clone(),
-                    title: Some(search_title.clone()),
-                    normalized: None,
-                    year: None,
-                    episodes: None,
-                });
-            }
+}
+
+/// Finds the first search title present verbatim among the candidate titles
+fn exact_raw(search: &PreparedSearch, candidate_titles: &NormalizedTitles) -> Option<TierMatch> {
+    search.titles.iter().find_map(|search_title| {
+        let (field, candidate_title, _) = candidate_titles.entries().find(|(_, t, _)| *t == search_title)?;
+
+        Some(TierMatch {
+            similarity: 1.0,
+            title: Some(search_title.clone()),
+            normalized: None,
+            candidate_title: candidate_title.clone(),
+            candidate_field: field,
+            year_gap: None,
+            episode_match: None,
+            format_match: None,
+        })
+    })
+}
+
+/// Finds the first normalized search title equal to a normalized candidate
+/// title, unless their seasons conflict
+fn exact_normalized(
+    search: &PreparedSearch,
+    candidate_titles: &NormalizedTitles,
+    config: &MatcherConfig,
+) -> Option<TierMatch> {
+    for (normalized_search_title, search_season) in search.normalized_titles.iter().zip(&search.seasons) {
+        let found = candidate_titles
+            .entries()
+            .zip(&candidate_titles.seasons)
+            .filter(|((_, _, n), _)| *n == normalized_search_title)
+            .find_map(|(entry, candidate_season)| {
+                let similarity = config.season_mismatch().apply(search_season, candidate_season, 1.0)?;
+                Some((entry, similarity))
+            });
+
+        if let Some(((field, candidate_title, _), similarity)) = found {
+            return Some(TierMatch {
+                similarity,
+                title: None,
+                normalized: Some(normalized_search_title.clone()),
+                candidate_title: candidate_title.clone(),
+                candidate_field: field,
+                year_gap: None,
+                episode_match: None,
+                format_match: None,
+            });
         }
     }
-    
-    // 6. Exact normalized title match
-    for candidate in results {
-        let candidate_data = get_anime_data(candidate);
-        let candidate_titles = get_all_titles(&candidate_data.title);
-        let normalized_candidate_titles: Vec<String> = candidate_titles
-            .iter()
-            .filter_map(|t| sanitize_title(Some(t)))
-            .collect();
-        
-        for normalized_search_title in &normalized_search_titles {
-            if normalized_candidate_titles.contains(normalized_search_title) {
-                return Some(MatchResult {
-                    similarity: 1.0,
-                    method: MatchMethod::ExactNormalized,
-                    result: candidate.clone(),
+
+    None
+}
+
+/// Finds the most similar normalized title pair scoring at least `threshold`,
+/// after weighting by title field and adjusting for conflicting seasons
+pub(crate) fn fuzzy(
+    search: &PreparedSearch,
+    candidate_titles: &NormalizedTitles,
+    threshold: f64,
+    metric: &dyn Similarity,
+    config: &MatcherConfig,
+) -> Option<TierMatch> {
+    let weights = config.title_weights();
+    let mut best: Option<TierMatch> = None;
+
+    for ((&search_field, normalized_search_title), search_season) in
+        search.fields.iter().zip(&search.normalized_titles).zip(&search.seasons)
+    {
+        for ((field, candidate_title, normalized_candidate_title), candidate_season) in
+            candidate_titles.entries().zip(&candidate_titles.seasons)
+        {
+            let Some(weight) = weights.pair(search_field, field) else {
+                continue;
+            };
+            let similarity = (metric.similarity(normalized_search_title, normalized_candidate_title) * weight).min(1.0);
+            let Some(similarity) = config.season_mismatch().apply(search_season, candidate_season, similarity) else {
+                continue;
+            };
+
+            if similarity >= threshold && best.as_ref().is_none_or(|b| similarity > b.similarity) {
+                best = Some(TierMatch {
+                    similarity,
                     title: None,
                     normalized: Some(normalized_search_title.clone()),
-                    year: None,
-                    episodes: None,
+                    candidate_title: candidate_title.clone(),
+                    candidate_field: field,
+                    year_gap: None,
+                    episode_match: None,
+                    format_match: None,
                 });
             }
         }
     }
-    
-    // 7. Loose match with year (similarity >= 0.8 and matching year)
-    if let Some(year) = search_year {
-        let mut best_loose_year_match: Option<MatchResult<T>> = None;
-        
-        for candidate in results {
-            let candidate_data = get_anime_data(candidate);
-            if candidate_data.year == Some(year) {
-                let candidate_titles = get_all_titles(&candidate_data.title);
-                let normalized_candidate_titles: Vec<String> = candidate_titles
-                    .iter()
-                    .filter_map(|t| sanitize_title(Some(t)))
-                    .collect();
-                
-                for normalized_search_title in &normalized_search_titles {
-                    for normalized_candidate_title in &normalized_candidate_titles {
-                        let similarity = jaro_winkler_distance(normalized_search_title, normalized_candidate_title, None);
-                        
-                        if similarity >= 0.8 && (best_loose_year_match.is_none() || similarity > best_loose_year_match.as_ref().unwrap().similarity) {
-                            best_loose_year_match = Some(MatchResult {
-                                similarity,
-                                method: MatchMethod::LooseYear,
-                                result: candidate.clone(),
-                                title: None,
-                                normalized: Some(normalized_search_title.clone()),
-                                year: Some(year),
-                                episodes: None,
-                            });
-                        }
-                    }
-                }
-            }
+
+    best
+}
+
+/// Why a candidate was not considered by a tier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Ineligible {
+    /// The search or the candidate has no year, or they are too far apart
+    Year,
+    /// The search or the candidate has no episode count, or they differ too much
+    Episodes,
+    /// The candidate's format differs from the searched one
+    Format,
+    /// The method is not a tier of the cascade
+    NotATier,
+}
+
+/// Year and episode comparison for a candidate that passed a tier's filters
+pub(crate) struct Eligibility {
+    year_gap: Option<u32>,
+    episode_match: Option<EpisodeMatch>,
+    format_match: Option<bool>,
+}
+
+/// Checks a candidate's year and episodes against the filters of a tier
+pub(crate) fn eligibility(
+    method: MatchMethod,
+    config: &MatcherConfig,
+    search: &PreparedSearch,
+    candidate_data: &ExpectAnime,
+) -> Result<Eligibility, Ineligible> {
+    if method == MatchMethod::Composite {
+        return Err(Ineligible::NotATier);
+    }
+
+    let year_gap = match (search.year, candidate_data.year) {
+        (Some(search_year), Some(candidate_year)) => Some(search_year.abs_diff(candidate_year)),
+        _ => None,
+    };
+    let episode_match = config.episode_tolerance().compare(search.episodes, candidate_data.episodes);
+    let format_match = format::compare(search.format, candidate_data.format);
+
+    if uses_year(method) && year_gap.is_none_or(|gap| gap > config.year_tolerance()) {
+        return Err(Ineligible::Year);
+    }
+
+    if uses_episodes(method) && episode_match.is_none() {
+        return Err(Ineligible::Episodes);
+    }
+
+    if config.format_mismatch() == FormatMismatch::Reject && format_match == Some(false) {
+        return Err(Ineligible::Format);
+    }
+
+    Ok(Eligibility {
+        year_gap: year_gap.filter(|_| uses_year(method)),
+        episode_match: episode_match.filter(|_| uses_episodes(method)),
+        format_match,
+    })
+}
+
+/// Compares a candidate against the search within a single tier of the cascade
+pub(crate) fn match_tier(
+    method: MatchMethod,
+    config: &MatcherConfig,
+    search: &PreparedSearch,
+    candidate_data: &ExpectAnime,
+    candidate_titles: &NormalizedTitles,
+) -> Option<TierMatch> {
+    let eligibility = eligibility(method, config, search, candidate_data).ok()?;
+
+    let mut tier_match = match method {
+        MatchMethod::ExactYearEpisodeRaw | MatchMethod::ExactYearRaw | MatchMethod::Exact => {
+            exact_raw(search, candidate_titles)
         }
-        
-        if let Some(match_result) = best_loose_year_match {
-            return Some(match_result);
+        MatchMethod::ExactYearEpisodeNormalized | MatchMethod::ExactYearNormalized | MatchMethod::ExactNormalized => {
+            exact_normalized(search, candidate_titles, config)
+        }
+        MatchMethod::LooseYear | MatchMethod::Loose | MatchMethod::LastResort | MatchMethod::NullMethod => {
+            fuzzy(
+                search,
+                candidate_titles,
+                config.threshold(method),
+                config.similarity(method),
+                config,
+            )
+        }
+        MatchMethod::Transliterated => fuzzy(
+            search.for_method(method)?,
+            candidate_titles,
+            config.threshold(method),
+            config.similarity(method),
+            config,
+        ),
+        // Not a tier of the cascade
+        MatchMethod::Composite => None,
+    }?;
+
+    // Penalties may take an exact match below 1.0, but not below the floor
+    let minimum = if method.is_exact() { config.exact_penalty_floor() } else { config.threshold(method) };
+
+    if let Some(gap) = eligibility.year_gap {
+        tier_match.year_gap = Some(gap);
+        tier_match.similarity = (tier_match.similarity - gap as f64 * config.year_penalty()).max(0.0);
+
+        if tier_match.similarity < minimum {
+            return None;
         }
     }
-    
-    // 8. Loose match (similarity >= 0.8)
-    let mut best_loose_match: Option<MatchResult<T>> = None;
-    
-    for candidate in results {
-        let candidate_data = get_anime_data(candidate);
-        let candidate_titles = get_all_titles(&candidate_data.title);
-        let normalized_candidate_titles: Vec<String> = candidate_titles
-            .iter()
-            .filter_map(|t| sanitize_title(Some(t)))
-            .collect();
-        
-        for normalized_search_title in &normalized_search_titles {
-            for normalized_candidate_title in &normalized_candidate_titles {
-                let similarity = jaro_winkler_distance(normalized_search_title, normalized_candidate_title, None);
-                
-                if similarity >= 0.8 && (best_loose_match.is_none() || similarity > best_loose_match.as_ref().unwrap().similarity) {
-                    best_loose_match = Some(MatchResult {
-                        similarity,
-                        method: MatchMethod::Loose,
-                        result: candidate.clone(),
-                        title: None,
-                        normalized: Some(normalized_search_title.clone()),
-                        year: None,
-                        episodes: None,
-                    });
-                }
-            }
+
+    if let (FormatMismatch::Penalty(penalty), Some(false)) = (config.format_mismatch(), eligibility.format_match) {
+        tier_match.similarity = (tier_match.similarity - penalty).max(0.0);
+
+        if tier_match.similarity < minimum {
+            return None;
         }
     }
-    
-    if let Some(match_result) = best_loose_match {
-        return Some(match_result);
-    }
-    
-    // 9. Last resort fuzzy match (similarity >= 0.7)
-    let mut best_fuzzy_match: Option<MatchResult<T>> = None;
-    
-    for candidate in results {
-        let candidate_data = get_anime_data(candidate);
-        let candidate_titles = get_all_titles(&candidate_data.title);
-        let normalized_candidate_titles: Vec<String> = candidate_titles
-            .iter()
-            .filter_map(|t| sanitize_title(Some(t)))
-            .collect();
-        
-        for normalized_search_title in &normalized_search_titles {
-            for normalized_candidate_title in &normalized_candidate_titles {
-                let similarity = jaro_winkler_distance(normalized_search_title, normalized_candidate_title, None);
-                
-                if similarity >= 0.7 && (best_fuzzy_match.is_none() || similarity > best_fuzzy_match.as_ref().unwrap().similarity) {
-                    best_fuzzy_match = Some(MatchResult {
-                        similarity,
-                        method: MatchMethod::LastResort,
-                        result: candidate.clone(),
-                        title: None,
-                        normalized: Some(normalized_search_title.clone()),
-                        year: None,
-                        episodes: None,
-                    });
-                }
-            }
+
+    tier_match.episode_match = eligibility.episode_match;
+    tier_match.format_match = eligibility.format_match;
+
+    Some(tier_match)
+}
+
+/// Whether a tier requires the candidate's episode count to match the search
+pub(crate) fn uses_episodes(method: MatchMethod) -> bool {
+    matches!(
+        method,
+        MatchMethod::ExactYearEpisodeRaw | MatchMethod::ExactYearEpisodeNormalized
+    )
+}
+
+/// Whether a tier requires the candidate's year to match the search
+pub(crate) fn uses_year(method: MatchMethod) -> bool {
+    matches!(
+        method,
+        MatchMethod::ExactYearEpisodeRaw
+            | MatchMethod::ExactYearEpisodeNormalized
+            | MatchMethod::ExactYearRaw
+            | MatchMethod::ExactYearNormalized
+            | MatchMethod::LooseYear
+    )
+}
+
+/// Scores every candidate with a composite score and returns those above the
+/// threshold, best first. Candidates of another format are left out when
+/// [`FormatMismatch::Reject`] is set.
+fn rank_composite(
+    scoring: &CompositeScoring,
+    config: &MatcherConfig,
+    search: &PreparedSearch,
+    candidates: &impl Candidates,
+) -> Vec<MatchResult<usize>> {
+    let metric = config.similarity(MatchMethod::Composite);
+    let mut ranked = Vec::new();
+
+    for index in 0..candidates.len() {
+        let candidate_data = candidates.anime(index);
+        let format_match = format::compare(search.format, candidate_data.format);
+
+        if config.format_mismatch() == FormatMismatch::Reject && format_match == Some(false) {
+            continue;
+        }
+
+        let Some(title_match) =
+            fuzzy(search, candidates.titles(index), f64::NEG_INFINITY, metric, config)
+        else {
+            continue;
+        };
+
+        let breakdown = scoring.score(
+            title_match.similarity,
+            (search.year, search.episodes, search.format),
+            (candidate_data.year, candidate_data.episodes, candidate_data.format),
+        );
+
+        if breakdown.composite >= scoring.threshold {
+            ranked.push(MatchResult {
+                similarity: breakdown.composite,
+                method: MatchMethod::Composite,
+                result: index,
+                title: None,
+                normalized: title_match.normalized,
+                candidate_title: Some(title_match.candidate_title),
+                matched_field: Some(title_match.candidate_field),
+                year: breakdown.year.and(search.year),
+                episodes: breakdown.episodes.and(search.episodes),
+                year_match: None,
+                episode_match: None,
+                format_match,
+                breakdown: Some(breakdown),
+            });
         }
     }
-    
-    if let Some(match_result) = best_fuzzy_match {
-        return Some(match_result);
-    }
-    
-    // 10. Check if there's any match with similarity >= 0.6
-    let mut best_possible_match: Option<MatchResult<T>> = None;
-    let mut highest_similarity = 0.0;
-    
-    for candidate in results {
-        let candidate_data = get_anime_data(candidate);
-        let candidate_titles = get_all_titles(&candidate_data.title);
-        let normalized_candidate_titles: Vec<String> = candidate_titles
-            .iter()
-            .filter_map(|t| sanitize_title(Some(t)))
-            .collect();
-        
-        for normalized_search_title in &normalized_search_titles {
-            for normalized_candidate_title in &normalized_candidate_titles {
-                let similarity = jaro_winkler_distance(normalized_search_title, normalized_candidate_title, None);
-                
-                if similarity > highest_similarity {
-                    highest_similarity = similarity;
-                    best_possible_match = Some(MatchResult {
-                        similarity,
-                        method: MatchMethod::NullMethod,
-                        result: candidate.clone(),
-                        title: None,
-                        normalized: Some(normalized_search_title.clone()),
-                        year: None,
-                        episodes: None,
-                    });
-                }
+
+    ranked.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+    ranked
+}
+
+/// Runs the cascade and returns the winning candidate's position
+pub(crate) fn best_match(
+    search: &PreparedSearch,
+    candidates: &impl Candidates,
+    config: &MatcherConfig,
+) -> Option<MatchResult<usize>> {
+    if let ScoringMode::Composite(scoring) = config.scoring() {
+        return rank_composite(&scoring, config, search, candidates).into_iter().next();
+    }
+
+    for &method in config.tiers() {
+        let mut best: Option<(TierMatch, usize)> = None;
+        let shortlist = candidates.shortlist(method, config, search);
+
+        for index in positions(&shortlist, candidates.len()) {
+            let Some(tier_match) =
+                match_tier(method, config, search, candidates.anime(index), candidates.titles(index))
+            else {
+                continue;
+            };
+
+            // A perfect match of the searched format cannot be beaten within its tier
+            if method.is_exact() && tier_match.similarity >= 1.0 && tier_match.preferred() {
+                return Some(tier_match.into_result(method, search, index));
+            }
+
+            if best.as_ref().is_none_or(|(b, _)| {
+                (tier_match.preferred(), tier_match.similarity) > (b.preferred(), b.similarity)
+            }) {
+                best = Some((tier_match, index));
             }
         }
-    }
-    
-    // Return None if similarity is less than 0.6
-    if let Some(match_result) = best_possible_match {
-        if match_result.similarity >= 0.6 {
-            return Some(match_result);
+
+        if let Some((tier_match, index)) = best {
+            return Some(tier_match.into_result(method, search, index));
         }
     }
-    
+
     None
 }
+
+/// Ranks every candidate by the strongest tier it matches, returning positions
+pub(crate) fn ranked_matches(
+    search: &PreparedSearch,
+    candidates: &impl Candidates,
+    n: usize,
+    config: &MatcherConfig,
+) -> Vec<MatchResult<usize>> {
+    if let ScoringMode::Composite(scoring) = config.scoring() {
+        let mut ranked = rank_composite(&scoring, config, search, candidates);
+        ranked.truncate(n);
+        return ranked;
+    }
+
+    let mut ranked: Vec<(usize, MatchResult<usize>)> = Vec::new();
+    let mut matched = vec![false; candidates.len()];
+
+    // Each candidate is ranked by the first tier it matches
+    for (rank, &method) in config.tiers().iter().enumerate() {
+        let shortlist = candidates.shortlist(method, config, search);
+
+        for index in positions(&shortlist, candidates.len()) {
+            if matched[index] {
+                continue;
+            }
+
+            if let Some(tier_match) =
+                match_tier(method, config, search, candidates.anime(index), candidates.titles(index))
+            {
+                matched[index] = true;
+                ranked.push((rank, tier_match.into_result(method, search, index)));
+            }
+        }
+    }
+
+    // Ties keep catalogue order, just like the cascade
+    ranked.sort_by(|(rank_a, a), (rank_b, b)| {
+        rank_a
+            .cmp(rank_b)
+            .then_with(|| (b.format_match != Some(false)).cmp(&(a.format_match != Some(false))))
+            .then_with(|| b.similarity.total_cmp(&a.similarity))
+            .then_with(|| a.result.cmp(&b.result))
+    });
+
+    ranked.into_iter().take(n).map(|(_, m)| m).collect()
+}
+
+/// Finds the best matching anime from a list of results based on the search criteria
+pub fn find_best_match<T: Clone>(
+    search: &ExpectAnime,
+    results: &[T],
+    get_anime_data: impl Fn(&T) -> &ExpectAnime,
+) -> Option<MatchResult<T>> {
+    find_best_match_with(search, results, get_anime_data, &MatcherConfig::default())
+}
+
+/// Like [`find_best_match`], but with the thresholds and tiers taken from `config`
+pub fn find_best_match_with<T: Clone>(
+    search: &ExpectAnime,
+    results: &[T],
+    get_anime_data: impl Fn(&T) -> &ExpectAnime,
+    config: &MatcherConfig,
+) -> Option<MatchResult<T>> {
+    if results.is_empty() {
+        return None;
+    }
+
+    let search = PreparedSearch::new(search, config.normalizer())?;
+    let candidates = LazyCandidates::new(results, get_anime_data, config.normalizer());
+
+    best_match(&search, &candidates, config).map(|m| m.map_result(|index| results[index].clone()))
+}
+
+/// Ranks every candidate by the strongest tier it matches, then by similarity,
+/// and returns at most `n` of them. The first entry is the same match that
+/// [`find_best_match`] would return.
+pub fn find_matches<T: Clone>(
+    search: &ExpectAnime,
+    results: &[T],
+    n: usize,
+    get_anime_data: impl Fn(&T) -> &ExpectAnime,
+) -> Vec<MatchResult<T>> {
+    find_matches_with(search, results, n, get_anime_data, &MatcherConfig::default())
+}
+
+/// Like [`find_matches`], but with the thresholds and tiers taken from `config`
+pub fn find_matches_with<T: Clone>(
+    search: &ExpectAnime,
+    results: &[T],
+    n: usize,
+    get_anime_data: impl Fn(&T) -> &ExpectAnime,
+    config: &MatcherConfig,
+) -> Vec<MatchResult<T>> {
+    let Some(search) = PreparedSearch::new(search, config.normalizer()) else {
+        return Vec::new();
+    };
+    let candidates = LazyCandidates::new(results, get_anime_data, config.normalizer());
+
+    ranked_matches(&search, &candidates, n, config)
+        .into_iter()
+        .map(|m| m.map_result(|index| results[index].clone()))
+        .collect()
+}
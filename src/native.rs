@@ -0,0 +1,121 @@
+use unicode_normalization::UnicodeNormalization;
+
+/// Old and variant kanji forms, with the standard form they fold to
+const KANJI_VARIANTS: &[(char, char)] = &[
+    ('亞', '亜'),
+    ('惡', '悪'),
+    ('壞', '壊'),
+    ('圓', '円'),
+    ('櫻', '桜'),
+    ('假', '仮'),
+    ('氣', '気'),
+    ('戀', '恋'),
+    ('劍', '剣'),
+    ('劔', '剣'),
+    ('藝', '芸'),
+    ('擊', '撃'),
+    ('黑', '黒'),
+    ('國', '国'),
+    ('號', '号'),
+    ('廣', '広'),
+    ('髙', '高'),
+    ('﨑', '崎'),
+    ('齋', '斎'),
+    ('齊', '斉'),
+    ('澤', '沢'),
+    ('兒', '児'),
+    ('實', '実'),
+    ('將', '将'),
+    ('獸', '獣'),
+    ('觸', '触'),
+    ('眞', '真'),
+    ('聲', '声'),
+    ('續', '続'),
+    ('戰', '戦'),
+    ('雙', '双'),
+    ('體', '体'),
+    ('對', '対'),
+    ('團', '団'),
+    ('鐵', '鉄'),
+    ('傳', '伝'),
+    ('當', '当'),
+    ('鬪', '闘'),
+    ('鬭', '闘'),
+    ('發', '発'),
+    ('萬', '万'),
+    ('邊', '辺'),
+    ('邉', '辺'),
+    ('變', '変'),
+    ('寶', '宝'),
+    ('與', '与'),
+    ('樂', '楽'),
+    ('龍', '竜'),
+    ('靈', '霊'),
+    ('學', '学'),
+    ('繪', '絵'),
+];
+
+/// Folds Japanese spelling variants in native titles so they compare equal.
+///
+/// - Half-width katakana become full width, then katakana become hiragana.
+/// - Long vowel marks (`ー`) are dropped.
+/// - Iteration marks (`ゝ`, `ゞ`, `ヽ`, `ヾ`, `々`, `〻`) repeat the character
+///   before them, voiced for `ゞ` and `ヾ`.
+/// - Middle dots between Japanese characters are dropped, so `シュタインズ・ゲート`
+///   and `シュタインズゲート` fold alike.
+/// - Common old kanji forms, such as `國` or `澤`, become their standard forms.
+///
+/// Latin text is only affected by the NFKC normalization the first step uses.
+pub fn fold_native(text: &str) -> String {
+    let mut folded: Vec<char> = Vec::with_capacity(text.len());
+    let chars: Vec<char> = text.nfkc().collect();
+
+    for (i, &c) in chars.iter().enumerate() {
+        match c {
+            'ー' => {}
+            'ゝ' | 'ヽ' | '々' | '〻' => {
+                if let Some(&previous) = folded.last() {
+                    folded.push(previous);
+                }
+            }
+            'ゞ' | 'ヾ' => {
+                if let Some(&previous) = folded.last() {
+                    folded.push(voiced(previous));
+                }
+            }
+            '・' | '·'
+                if is_japanese_at(&chars, i.checked_sub(1))
+                    && is_japanese_at(&chars, Some(i + 1)) => {}
+            _ => folded.push(fold_char(c)),
+        }
+    }
+
+    folded.into_iter().collect()
+}
+
+fn fold_char(c: char) -> char {
+    match c {
+        // Katakana ァ to ヶ sit 0x60 after their hiragana
+        'ァ'..='ヶ' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+        _ => KANJI_VARIANTS
+            .iter()
+            .find(|(variant, _)| *variant == c)
+            .map_or(c, |&(_, standard)| standard),
+    }
+}
+
+/// The voiced form of a kana, such as `が` for `か`, or the kana itself
+fn voiced(c: char) -> char {
+    format!("{c}\u{3099}").nfc().next().unwrap_or(c)
+}
+
+fn is_japanese_at(chars: &[char], index: Option<usize>) -> bool {
+    index
+        .and_then(|i| chars.get(i))
+        .is_some_and(|&c| is_japanese(c))
+}
+
+/// Whether a character is kana, a kanji or a Japanese mark
+fn is_japanese(c: char) -> bool {
+    matches!(c, '\u{3040}'..='\u{30FF}' | '\u{3005}' | '\u{303B}' | '\u{4E00}'..='\u{9FFF}' | '\u{F900}'..='\u{FAFF}')
+}
@@ -0,0 +1,288 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::config::MatcherConfig;
+use crate::r#match::{
+    AnimeTitle, ExpectAnime, MatchResult, PreparedSearch, TitleField, best_match, clean_title, get_all_titles,
+    ranked_matches,
+};
+use crate::native::fold_native;
+use crate::romaji::canonicalize_romaji;
+use crate::rules::NormalizationRules;
+use crate::season::SeasonInfo;
+
+/// The title normalization pipeline behind [`sanitize_title`](crate::sanitize_title),
+/// with its patterns compiled once.
+///
+/// Build one and reuse it; [`Normalizer::default`] shares a process-wide
+/// instance's patterns rather than recompiling them.
+#[derive(Debug, Clone)]
+pub struct Normalizer {
+    season: Regex,
+    numbered_season: Regex,
+    tags: Vec<String>,
+    format: Regex,
+    rules: NormalizationRules,
+}
+
+static DEFAULT: LazyLock<Normalizer> = LazyLock::new(Normalizer::compile);
+
+impl Default for Normalizer {
+    fn default() -> Self {
+        DEFAULT.clone()
+    }
+}
+
+impl Normalizer {
+    /// Format, release and version tags removed from titles by default,
+    /// matched as whole words regardless of case. Bare "TV" and "WEB" are left
+    /// out since they are also title words; "(TV)" goes with the other
+    /// parenthesized text.
+    pub const DEFAULT_TAGS: &[&str] = &[
+        "uncut",
+        "uncensored",
+        "censored",
+        "dub",
+        "dubbed",
+        "sub",
+        "subbed",
+        "the final chapters",
+        "BD",
+        "BDRip",
+        "Blu-ray",
+        "DVD",
+        "DVDRip",
+        "WEB-DL",
+        "WEBRip",
+        "480p",
+        "720p",
+        "1080p",
+        "2160p",
+        "4K",
+        "HEVC",
+        "AVC",
+        "x264",
+        "x265",
+        "10bit",
+        "Dual Audio",
+        "Multi Audio",
+    ];
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn compile() -> Self {
+        let tags: Vec<String> = Self::DEFAULT_TAGS.iter().map(|tag| tag.to_string()).collect();
+
+        Self {
+            season: Regex::new(r"\b(season|cour|part|chapter|special)\b").unwrap(),
+            numbered_season: Regex::new(r"(\d+)(?:th|rd|nd|st)?\s*(?:season|cour|part|chapter|special)\b").unwrap(),
+            format: compile_tags(&tags),
+            tags,
+            rules: NormalizationRules::default(),
+        }
+    }
+
+    /// Replaces the tags removed from titles, such as `BD` or `Dual Audio`.
+    /// Tags match as whole words regardless of case, and any run of spaces,
+    /// dots, dashes or underscores matches the space between two words.
+    /// Parenthesized text like `(TV)` is always removed.
+    pub fn with_tags(mut self, tags: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.tags = tags.into_iter().map(Into::into).collect();
+        self.format = compile_tags(&self.tags);
+        self
+    }
+
+    /// The tags removed from titles.
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// Adds custom removals, substitutions and aliases to the pipeline,
+    /// replacing any set before.
+    pub fn with_rules(mut self, rules: NormalizationRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// The custom rules run on every title.
+    pub fn rules(&self) -> &NormalizationRules {
+        &self.rules
+    }
+
+    /// The shared default instance.
+    pub(crate) fn shared() -> &'static Normalizer {
+        &DEFAULT
+    }
+
+    /// Sanitizes a title by removing unnecessary words and characters for comparison.
+    pub fn sanitize(&self, title: &str) -> String {
+        let mut sanitized = title.to_lowercase();
+
+        // Apply custom rules, before anything else is taken out
+        if !self.rules.is_empty() {
+            sanitized = self.rules.apply(&sanitized);
+        }
+
+        // Remove tags related to anime formats or additional information,
+        // while their punctuation is intact
+        sanitized = self.format.replace_all(&sanitized, " ").into_owned();
+
+        // Fold kana, long vowel marks and kanji variants in native titles
+        sanitized = fold_native(&sanitized);
+
+        // Replace chapters with chapter
+        sanitized = sanitized.replace("chapters", "chapter");
+
+        // Remove specific words related to anime seasons or parts
+        sanitized = self.season.replace_all(&sanitized, "").into_owned();
+
+        // Remove specific words related to anime seasons or parts with numbers
+        sanitized = self.numbered_season.replace_all(&sanitized, " $1 ").into_owned();
+
+        // Remove non-alphanumeric characters
+        sanitized = sanitized
+            .chars()
+            .map(|c| if c.is_alphanumeric() || c.is_whitespace() { c } else { ' ' })
+            .collect();
+
+        // Normalize the string to remove accents and other diacritical marks
+        sanitized = sanitized
+            .nfd()
+            .filter(|c| !unicode_categories::UnicodeCategories::is_mark(*c))
+            .collect();
+
+        // Spell romaji words the same way, whatever the romanization
+        sanitized = canonicalize_romaji(&sanitized);
+
+        clean_title(Some(&sanitized)).unwrap_or_default()
+    }
+
+    /// Collects and sanitizes every title of an anime.
+    pub fn normalize_titles(&self, title: &Option<AnimeTitle>) -> NormalizedTitles {
+        let (fields, raw): (Vec<_>, Vec<_>) = get_all_titles(title).into_iter().unzip();
+        let normalized = raw.iter().map(|t| self.sanitize(t)).collect();
+        let seasons = raw.iter().map(|t| SeasonInfo::parse(t)).collect();
+        NormalizedTitles {
+            raw,
+            normalized,
+            fields,
+            seasons,
+        }
+    }
+
+    /// Sanitizes the titles of a whole catalogue once, for reuse across searches.
+    pub fn normalize_catalogue<'a, T>(
+        &self,
+        results: &'a [T],
+        get_anime_data: impl Fn(&'a T) -> &'a ExpectAnime,
+    ) -> NormalizedCatalogue<'a, T> {
+        let entries = results
+            .iter()
+            .map(|candidate| {
+                let anime = get_anime_data(candidate);
+                (anime, self.normalize_titles(&anime.title))
+            })
+            .collect();
+
+        NormalizedCatalogue {
+            results,
+            entries,
+            normalizer: self.clone(),
+        }
+    }
+}
+
+/// Builds the pattern removing parenthesized text and the given tags, trying
+/// longer tags first so that `WEB-DL` is not cut short by `WEB`
+fn compile_tags(tags: &[String]) -> Regex {
+    let mut tags: Vec<&String> = tags.iter().filter(|tag| !tag.trim().is_empty()).collect();
+    tags.sort_by_key(|tag| std::cmp::Reverse(tag.len()));
+
+    let words: Vec<String> = tags
+        .iter()
+        .map(|tag| {
+            let words: Vec<String> = tag.split_whitespace().map(regex::escape).collect();
+            words.join(r"[\s._-]+")
+        })
+        .collect();
+
+    let mut pattern = String::from(r"\([^)]*\)");
+    if !words.is_empty() {
+        pattern.push_str(&format!(r"|(?i:\b(?:{})\b)", words.join("|")));
+    }
+
+    Regex::new(&pattern).unwrap()
+}
+
+/// An anime's titles alongside their sanitized forms, the fields they came
+/// from and the seasons they name, in the same order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NormalizedTitles {
+    pub raw: Vec<String>,
+    pub normalized: Vec<String>,
+    pub fields: Vec<TitleField>,
+    pub seasons: Vec<SeasonInfo>,
+}
+
+impl NormalizedTitles {
+    /// Raw and sanitized title pairs.
+    pub fn pairs(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.raw.iter().zip(&self.normalized)
+    }
+
+    /// Fields with their raw and sanitized titles.
+    pub fn entries(&self) -> impl Iterator<Item = (TitleField, &String, &String)> {
+        self.fields.iter().zip(self.pairs()).map(|(&field, (raw, normalized))| (field, raw, normalized))
+    }
+}
+
+/// A catalogue with every title sanitized up front, built by
+/// [`Normalizer::normalize_catalogue`].
+///
+/// Searches are sanitized with the same normalizer as the catalogue, whatever
+/// normalizer the [`MatcherConfig`] passed to them carries.
+#[derive(Debug, Clone)]
+pub struct NormalizedCatalogue<'a, T> {
+    pub(crate) results: &'a [T],
+    pub(crate) entries: Vec<(&'a ExpectAnime, NormalizedTitles)>,
+    pub(crate) normalizer: Normalizer,
+}
+
+impl<T> NormalizedCatalogue<'_, T> {
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The titles of the candidate at `index`.
+    pub fn titles(&self, index: usize) -> Option<&NormalizedTitles> {
+        self.entries.get(index).map(|(_, titles)| titles)
+    }
+}
+
+impl<T: Clone> NormalizedCatalogue<'_, T> {
+    /// Same as [`find_best_match_with`](crate::find_best_match_with) over the whole catalogue.
+    pub fn find_best_match(&self, search: &ExpectAnime, config: &MatcherConfig) -> Option<MatchResult<T>> {
+        let search = PreparedSearch::new(search, &self.normalizer)?;
+        best_match(&search, self, config).map(|m| m.map_result(|index| self.results[index].clone()))
+    }
+
+    /// Same as [`find_matches_with`](crate::find_matches_with) over the whole catalogue.
+    pub fn find_matches(&self, search: &ExpectAnime, n: usize, config: &MatcherConfig) -> Vec<MatchResult<T>> {
+        let Some(search) = PreparedSearch::new(search, &self.normalizer) else {
+            return Vec::new();
+        };
+
+        ranked_matches(&search, self, n, config)
+            .into_iter()
+            .map(|m| m.map_result(|index| self.results[index].clone()))
+            .collect()
+    }
+}
@@ -0,0 +1,86 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use crate::normalize::NormalizedTitles;
+use crate::similarity::ngrams;
+
+/// Candidate pruning for the fuzzy tiers of a [`MatchIndex`](crate::MatchIndex).
+///
+/// Before the similarity metric runs, candidates are ranked by how many
+/// distinct character n-grams their normalized titles share with the search,
+/// and only the best `shortlist` of them are scored. A candidate the metric
+/// would have picked can be pruned away, so this trades recall for speed;
+/// [`MatchIndex::recall`](crate::MatchIndex::recall) measures how much against
+/// brute force. A `shortlist` at least as large as the catalogue prunes
+/// nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pruning {
+    /// Maximum number of candidates each fuzzy tier scores.
+    pub shortlist: usize,
+    /// Length of the n-grams candidates are retrieved by.
+    pub n: usize,
+}
+
+impl Default for Pruning {
+    fn default() -> Self {
+        Self { shortlist: 200, n: 3 }
+    }
+}
+
+/// Inverted index from character n-grams to the candidates whose normalized
+/// titles contain them
+#[derive(Debug, Clone)]
+pub(crate) struct NgramIndex {
+    pruning: Pruning,
+    postings: HashMap<Vec<char>, Vec<usize>>,
+}
+
+impl NgramIndex {
+    pub(crate) fn new<'a>(pruning: Pruning, titles: impl Iterator<Item = &'a NormalizedTitles>) -> Self {
+        let mut postings: HashMap<Vec<char>, Vec<usize>> = HashMap::new();
+
+        for (index, titles) in titles.enumerate() {
+            for title in &titles.normalized {
+                for gram in ngrams(title, pruning.n).into_keys() {
+                    let positions = postings.entry(gram).or_default();
+
+                    if positions.last() != Some(&index) {
+                        positions.push(index);
+                    }
+                }
+            }
+        }
+
+        Self { pruning, postings }
+    }
+
+    pub(crate) fn pruning(&self) -> Pruning {
+        self.pruning
+    }
+
+    /// Keeps the `shortlist` positions sharing the most n-grams with the
+    /// search titles, in ascending order. Ties go to the earlier position.
+    pub(crate) fn prune(&self, search_titles: &[String], positions: Vec<usize>, len: usize) -> Vec<usize> {
+        if positions.len() <= self.pruning.shortlist {
+            return positions;
+        }
+
+        let mut shared = vec![0u32; len];
+        let mut grams: Vec<Vec<char>> =
+            search_titles.iter().flat_map(|title| ngrams(title, self.pruning.n).into_keys()).collect();
+        grams.sort_unstable();
+        grams.dedup();
+
+        for gram in &grams {
+            for &index in self.postings.get(gram).into_iter().flatten() {
+                shared[index] += 1;
+            }
+        }
+
+        let mut positions = positions;
+        positions.select_nth_unstable_by_key(self.pruning.shortlist, |&index| (Reverse(shared[index]), index));
+        positions.truncate(self.pruning.shortlist);
+        positions.sort_unstable();
+        positions
+    }
+}
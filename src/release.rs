@@ -0,0 +1,179 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::r#match::{AnimeTitle, ExpectAnime};
+use crate::season::SeasonInfo;
+
+/// The parts of a release filename such as
+/// `[SubsPlease] Kimetsu no Yaiba - 05 (1080p) [ABCD1234].mkv`.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ParsedRelease {
+    /// The release group, from a leading bracket
+    pub group: Option<String>,
+    /// The title as written, season markers included
+    pub title: String,
+    /// The episode number, not the episode count
+    pub episode: Option<u32>,
+    pub season: Option<u32>,
+    /// The release version, 2 for `05v2`
+    pub version: Option<u32>,
+    pub year: Option<i32>,
+    /// Such as `1080p` or `1920x1080`
+    pub resolution: Option<String>,
+    /// Such as `BD`, `WEB-DL` or `DVDRip`
+    pub source: Option<String>,
+    /// Such as `HEVC` or `x264`
+    pub codec: Option<String>,
+    /// The CRC32 checksum, from a bracket of eight hex digits
+    pub checksum: Option<String>,
+}
+
+struct Patterns {
+    extension: Regex,
+    group: Regex,
+    brackets: Regex,
+    checksum: Regex,
+    year: Regex,
+    resolution: Regex,
+    source: Regex,
+    codec: Regex,
+    version: Regex,
+    episodes: Vec<Regex>,
+}
+
+static PATTERNS: LazyLock<Patterns> = LazyLock::new(|| {
+    let compile = |pattern: &str| Regex::new(pattern).unwrap();
+
+    Patterns {
+        extension: compile(r"(?i)\.(?:mkv|mp4|avi|webm|m4v|ts|wmv|flv|ogm)$"),
+        group: compile(r"^\s*[\[【]([^\]】]+)[\]】]"),
+        brackets: compile(r"\[[^\]]*\]|【[^】]*】|\([^)]*\)|\{[^}]*\}"),
+        checksum: compile(r"[\[(]([0-9A-Fa-f]{8})[\])]"),
+        year: compile(r"[\[(]((?:19|20)\d{2})[\])]"),
+        resolution: compile(r"(?i)\b(\d{3,4}[pi]|\d{3,4}x\d{3,4}|4k)\b"),
+        source: compile(r"(?i)\b(blu-?ray|bdrip|bd|web-?dl|web-?rip|web|dvdrip|dvd|hdtv)\b"),
+        codec: compile(r"(?i)\b(x26[45]|h\.?26[45]|hevc|avc|av1|xvid)\b"),
+        version: compile(r"(?i)\bv(\d)\b"),
+        episodes: [
+            r"(?i)\bs(\d{1,2})\s*e(\d{1,4})(?:v(\d))?\b",
+            r"()\s-\s*(\d{1,4})(?:v(\d))?\b",
+            r"(?i)()\b(?:episode|ep)\.?\s*(\d{1,4})(?:v(\d))?\b",
+            r"(?i)()\be(\d{1,4})(?:v(\d))?\b",
+        ]
+        .into_iter()
+        .map(compile)
+        .collect(),
+    }
+});
+
+impl ParsedRelease {
+    /// Parses a release filename, or returns `None` when no title is left
+    /// once the tags are taken out.
+    ///
+    /// Bracketed tags may appear anywhere. Unbracketed ones, as in
+    /// `Show.S01E05.1080p.WEB-DL.x264`, end the title, unless they start it or
+    /// are followed by more of the title.
+    pub fn parse(filename: &str) -> Option<Self> {
+        let patterns = &*PATTERNS;
+        let name = patterns.extension.replace(filename.trim(), "");
+        let tag = |pattern: &Regex| pattern.captures(&name).map(|captures| captures[1].to_string());
+
+        let group = tag(&patterns.group);
+        let checksum = tag(&patterns.checksum).map(|checksum| checksum.to_uppercase());
+        let year = tag(&patterns.year).and_then(|year| year.parse().ok());
+
+        let mut text = patterns.brackets.replace_all(&name, " ").replace('_', " ");
+        if !text.trim().contains(' ') {
+            text = text.replace('.', " ");
+        }
+
+        // The title ends at the episode number, or earlier at an unbracketed
+        // tag that follows some title text and ends it or is followed by
+        // another tag, so a title word such as `Web` or `DVD` is kept
+        let mut end = text.len();
+        let mut season = None;
+        let mut episode = None;
+        let mut version = None;
+
+        if let Some(captures) = patterns.episodes.iter().find_map(|pattern| pattern.captures(&text)) {
+            end = captures.get(0).unwrap().start();
+            season = captures.get(1).and_then(|m| m.as_str().parse().ok());
+            episode = captures[2].parse().ok();
+            version = captures.get(3).and_then(|m| m.as_str().parse().ok());
+        }
+
+        let tag_patterns = [&patterns.resolution, &patterns.source, &patterns.codec];
+        let mut tags: Vec<(usize, usize)> = tag_patterns
+            .iter()
+            .flat_map(|pattern| pattern.find_iter(&text[..end]).map(|m| (m.start(), m.end())))
+            .collect();
+        tags.sort_unstable();
+
+        let ends_title = |&(start, tag_end): &(usize, usize)| {
+            let rest = text[tag_end..end].trim_start_matches([' ', '-', '.']);
+            !text[..start].trim().is_empty()
+                && (rest.trim().is_empty() || tags.iter().any(|&(next, _)| next == end - rest.len()))
+        };
+        if let Some(&(start, _)) = tags.iter().find(|&tag| ends_title(tag)) {
+            end = start;
+        }
+
+        let title = text[..end].trim().trim_end_matches(['-', '.', ' ']).trim().to_string();
+        if title.is_empty() {
+            return None;
+        }
+
+        // Tags are read from the brackets and after the title, never from the
+        // title itself
+        let outside: Vec<&str> = patterns.brackets.find_iter(&name).map(|m| m.as_str()).chain([&text[end..]]).collect();
+        let outside = outside.join(" ");
+        let release_tag = |pattern: &Regex| pattern.captures(&outside).map(|captures| captures[1].to_string());
+
+        Some(Self {
+            group,
+            season: season.or_else(|| SeasonInfo::parse(&title).season),
+            version: version.or_else(|| tag(&patterns.version).and_then(|v| v.parse().ok())),
+            title,
+            episode,
+            year,
+            resolution: release_tag(&patterns.resolution),
+            source: release_tag(&patterns.source),
+            codec: release_tag(&patterns.codec),
+            checksum,
+        })
+    }
+}
+
+impl From<&ParsedRelease> for ExpectAnime {
+    /// A search for the release's title and year. The title goes in the
+    /// untyped `user_preferred` field, since its language is unknown, with the
+    /// season appended when only the tags named it. The episode number is not
+    /// an episode count, so `episodes` is left empty.
+    fn from(release: &ParsedRelease) -> Self {
+        let title = match release.season {
+            Some(season) if season > 1 && SeasonInfo::parse(&release.title).season.is_none() => {
+                format!("{} Season {season}", release.title)
+            }
+            _ => release.title.clone(),
+        };
+
+        ExpectAnime {
+            id: None,
+            title: Some(AnimeTitle {
+                user_preferred: Some(title),
+                ..Default::default()
+            }),
+            year: release.year,
+            episodes: None,
+            format: None,
+        }
+    }
+}
+
+impl From<ParsedRelease> for ExpectAnime {
+    fn from(release: ParsedRelease) -> Self {
+        ExpectAnime::from(&release)
+    }
+}
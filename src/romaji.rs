@@ -0,0 +1,181 @@
+/// Rewrites romaji words into one canonical Hepburn spelling, so that
+/// variants of the same reading compare equal.
+///
+/// - Kunrei and Nihon-shiki syllables become Hepburn: `si`, `ti`, `tu`, `hu`,
+///   `zi`, `di`, `du`, `sya`, `tya`, `zya`, `jya` become `shi`, `chi`, `tsu`,
+///   `fu`, `ji`, `ji`, `zu`, `sha`, `cha`, `ja`, `ja`.
+/// - Long vowels are shortened, whether doubled (`aa`, `ii`, `uu`, `ee`, `oo`),
+///   written `ou` or `oh`. Macrons are expected to have been stripped already,
+///   which shortens them the same way.
+/// - `m` before `b`, `m` or `p` becomes `n`, and `cch` becomes `tch`.
+/// - The particles `wo`, `ha` and `he` become `o`, `wa` and `e`.
+///
+/// Words are only rewritten when they read as a whole sequence of Japanese
+/// syllables, so most English words are left alone. The few that do read as
+/// romaji, like "titan", are rewritten on both sides of a comparison alike.
+/// Roman numerals such as `ii` and `viii` are kept as written. Expects lowercase text.
+pub fn canonicalize_romaji(text: &str) -> String {
+    text.split_whitespace()
+        .map(|word| canonicalize_word(word).unwrap_or_else(|| word.to_string()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn canonicalize_word(word: &str) -> Option<String> {
+    match word {
+        "wo" => return Some("o".into()),
+        "ha" => return Some("wa".into()),
+        "he" => return Some("e".into()),
+        _ => {}
+    }
+
+    if !word.bytes().all(|b| b.is_ascii_lowercase()) {
+        return None;
+    }
+
+    // Roman numerals such as "ii" or "viii" would lose their length
+    if word.bytes().all(|b| matches!(b, b'i' | b'v' | b'x')) {
+        return None;
+    }
+
+    let morae = parse(word.as_bytes())?;
+    let mut out = String::with_capacity(word.len());
+    let mut previous_vowel = None;
+    let mut geminate = false;
+
+    for mora in morae {
+        match mora {
+            Mora::Geminate => geminate = true,
+            Mora::N => {
+                out.push('n');
+                previous_vowel = None;
+            }
+            Mora::LongO => {}
+            Mora::Syllable(onset, vowel) => {
+                let onset = canonical_onset(onset, vowel);
+
+                // A bare vowel lengthening the one before it
+                if onset.is_empty() && is_long_vowel(previous_vowel, vowel) {
+                    continue;
+                }
+
+                if geminate {
+                    out.push(if onset.starts_with("ch") {
+                        't'
+                    } else {
+                        onset.chars().next()?
+                    });
+                    geminate = false;
+                }
+
+                out.push_str(onset);
+                out.push(vowel as char);
+                previous_vowel = Some(vowel);
+            }
+        }
+    }
+
+    // A trailing geminate mark is not romaji
+    if geminate {
+        return None;
+    }
+
+    Some(out)
+}
+
+enum Mora<'a> {
+    /// A consonant doubled into the next syllable
+    Geminate,
+    /// The syllabic n
+    N,
+    /// The `h` of a long `oh`
+    LongO,
+    Syllable(&'a [u8], u8),
+}
+
+const ONSETS: [&str; 35] = [
+    "sh", "ch", "ts", "ky", "gy", "sy", "zy", "jy", "ty", "cy", "dy", "ny", "hy", "by", "py", "my",
+    "ry", "k", "g", "s", "z", "t", "d", "n", "h", "b", "p", "m", "y", "r", "w", "f", "j", "v", "",
+];
+
+fn is_vowel(b: u8) -> bool {
+    matches!(b, b'a' | b'i' | b'u' | b'e' | b'o')
+}
+
+/// Splits a word into morae, or `None` when it is not romaji
+fn parse(word: &[u8]) -> Option<Vec<Mora<'_>>> {
+    let mut morae = Vec::new();
+    let mut pos = 0;
+
+    while pos < word.len() {
+        let rest = &word[pos..];
+        let next = rest.get(1).copied();
+
+        // Doubled consonants, and the t of tch
+        if !is_vowel(rest[0])
+            && rest[0] != b'n'
+            && (next == Some(rest[0]) || rest.starts_with(b"tch"))
+        {
+            morae.push(Mora::Geminate);
+            pos += 1;
+            continue;
+        }
+
+        // The syllabic n, written m before labials in some Hepburn styles
+        if (rest[0] == b'n' && next.is_none_or(|b| !is_vowel(b) && b != b'y'))
+            || (rest[0] == b'm' && next.is_some_and(|b| matches!(b, b'b' | b'm' | b'p')))
+        {
+            morae.push(Mora::N);
+            pos += 1;
+            continue;
+        }
+
+        if rest[0] == b'h'
+            && matches!(morae.last(), Some(Mora::Syllable(_, b'o')))
+            && next.is_none_or(|b| !is_vowel(b) && b != b'y')
+        {
+            morae.push(Mora::LongO);
+            pos += 1;
+            continue;
+        }
+
+        let onset = ONSETS.iter().map(|onset| onset.as_bytes()).find(|onset| {
+            rest.starts_with(onset) && rest.get(onset.len()).copied().is_some_and(is_vowel)
+        })?;
+
+        morae.push(Mora::Syllable(onset, rest[onset.len()]));
+        pos += onset.len() + 1;
+    }
+
+    Some(morae)
+}
+
+/// The Hepburn onset for a syllable
+fn canonical_onset(onset: &[u8], vowel: u8) -> &'static str {
+    match (onset, vowel) {
+        (b"s", b'i') => "sh",
+        (b"z" | b"d", b'i') => "j",
+        (b"t", b'i') => "ch",
+        (b"t", b'u') => "ts",
+        (b"d", b'u') => "z",
+        (b"h", b'u') => "f",
+        (b"w", b'o') => "",
+        (b"sy", _) => "sh",
+        (b"ty" | b"cy", _) => "ch",
+        (b"zy" | b"jy" | b"dy", _) => "j",
+        _ => ONSETS
+            .iter()
+            .find(|o| o.as_bytes() == onset)
+            .copied()
+            .unwrap_or(""),
+    }
+}
+
+/// Whether a bare vowel after `previous` only lengthens it
+fn is_long_vowel(previous: Option<u8>, vowel: u8) -> bool {
+    match previous {
+        Some(b'o') => matches!(vowel, b'o' | b'u'),
+        Some(previous) => previous == vowel,
+        None => false,
+    }
+}
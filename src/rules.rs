@@ -0,0 +1,227 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::Path;
+
+use regex::{Captures, Regex};
+use serde::Deserialize;
+use toml::Spanned;
+
+/// Extra rewrites for [`Normalizer`](crate::Normalizer), loaded from a TOML
+/// file such as:
+///
+/// ```toml
+/// # Words and phrases removed from titles
+/// remove = ["the animation", "tv anime", "gekijouban"]
+///
+/// # Regex substitutions, applied in order
+/// [[replace]]
+/// pattern = '\bmovie\s*(\d+)'
+/// with = "$1"
+///
+/// # Words and phrases spelled another way
+/// [aliases]
+/// aot = "attack on titan"
+/// "boku no" = "my"
+/// ```
+///
+/// Rules see the title lowercased, before format tags, punctuation or
+/// anything else is removed, so patterns and words should be written in
+/// lowercase. Whatever a substitution or alias writes is lowercased too.
+/// Removals and aliases match whole words, and a phrase matches any run of
+/// spaces between its words. Substitutions run first, then removals and
+/// aliases in a single pass, so an alias is never rewritten again.
+#[derive(Debug, Clone, Default)]
+pub struct NormalizationRules {
+    replacements: Vec<(Regex, String)>,
+    words: Option<Regex>,
+    rewrites: HashMap<String, String>,
+}
+
+/// A problem with one line of a rules file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleError {
+    /// 1-based line number
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// Why a rules file could not be loaded.
+#[derive(Debug)]
+pub enum RulesError {
+    Io(std::io::Error),
+    /// Every problem found in the file, in line order
+    Invalid(Vec<RuleError>),
+}
+
+impl fmt::Display for RulesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RulesError::Io(error) => write!(f, "could not read rules: {error}"),
+            RulesError::Invalid(errors) => {
+                let lines: Vec<String> = errors.iter().map(RuleError::to_string).collect();
+                write!(f, "invalid rules:\n{}", lines.join("\n"))
+            }
+        }
+    }
+}
+
+impl std::error::Error for RulesError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RulesError::Io(error) => Some(error),
+            RulesError::Invalid(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for RulesError {
+    fn from(error: std::io::Error) -> Self {
+        RulesError::Io(error)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RulesFile {
+    #[serde(default)]
+    remove: Vec<Spanned<String>>,
+    #[serde(default)]
+    replace: Vec<ReplaceRule>,
+    #[serde(default)]
+    aliases: BTreeMap<String, Spanned<String>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ReplaceRule {
+    pattern: Spanned<String>,
+    #[serde(default)]
+    with: String,
+}
+
+impl NormalizationRules {
+    /// Reads and compiles a rules file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, RulesError> {
+        Self::from_toml(&std::fs::read_to_string(path)?)
+    }
+
+    /// Compiles rules written in TOML, reporting every invalid rule with
+    /// its line.
+    pub fn from_toml(text: &str) -> Result<Self, RulesError> {
+        let line = |offset: usize| text[..offset.min(text.len())].matches('\n').count() + 1;
+
+        let file: RulesFile = toml::from_str(text).map_err(|error| {
+            RulesError::Invalid(vec![RuleError {
+                line: error.span().map_or(1, |span| line(span.start)),
+                message: error.message().to_string(),
+            }])
+        })?;
+
+        let mut errors = Vec::new();
+        let mut replacements = Vec::new();
+        let mut rewrites: HashMap<String, String> = HashMap::new();
+        let mut lines: HashMap<String, usize> = HashMap::new();
+
+        for rule in &file.replace {
+            match Regex::new(rule.pattern.get_ref()) {
+                Ok(pattern) => replacements.push((pattern, rule.with.clone())),
+                Err(error) => errors.push(RuleError {
+                    line: line(rule.pattern.span().start),
+                    message: format!("invalid pattern `{}`: {error}", rule.pattern.get_ref()),
+                }),
+            }
+        }
+
+        let removals = file.remove.iter().map(|word| (line(word.span().start), word.get_ref().as_str(), ""));
+        let aliases = file
+            .aliases
+            .iter()
+            .map(|(word, alias)| (line(alias.span().start), word.as_str(), alias.get_ref().as_str()));
+        let mut words: Vec<_> = removals.chain(aliases).collect();
+        words.sort_by_key(|&(at, _, _)| at);
+
+        for (at, word, rewrite) in words {
+            let key = phrase_key(word);
+
+            if key.is_empty() {
+                errors.push(RuleError {
+                    line: at,
+                    message: "empty word".into(),
+                });
+            } else if let Some(&first) = lines.get(&key) {
+                errors.push(RuleError {
+                    line: at,
+                    message: format!("`{word}` is already rewritten on line {first}"),
+                });
+            } else {
+                lines.insert(key.clone(), at);
+                rewrites.insert(key, rewrite.to_lowercase());
+            }
+        }
+
+        if !errors.is_empty() {
+            errors.sort_by_key(|error| error.line);
+            return Err(RulesError::Invalid(errors));
+        }
+
+        Ok(Self {
+            replacements,
+            words: compile_words(rewrites.keys()),
+            rewrites,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.replacements.is_empty() && self.rewrites.is_empty()
+    }
+
+    /// Runs the substitutions, then the removals and aliases, on a
+    /// lowercased title
+    pub(crate) fn apply(&self, title: &str) -> String {
+        let mut text = title.to_string();
+
+        for (pattern, with) in &self.replacements {
+            text = pattern.replace_all(&text, with.as_str()).to_lowercase();
+        }
+
+        if let Some(words) = &self.words {
+            text = words
+                .replace_all(&text, |captures: &Captures| {
+                    match self.rewrites.get(&phrase_key(&captures[0])) {
+                        Some(rewrite) => format!(" {rewrite} "),
+                        None => captures[0].to_string(),
+                    }
+                })
+                .into_owned();
+        }
+
+        text
+    }
+}
+
+/// A word or phrase in lowercase, its words separated by single spaces
+fn phrase_key(phrase: &str) -> String {
+    phrase.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+/// Matches any of the phrases as whole words, longer phrases first
+fn compile_words<'a>(phrases: impl Iterator<Item = &'a String>) -> Option<Regex> {
+    let mut phrases: Vec<&String> = phrases.collect();
+    if phrases.is_empty() {
+        return None;
+    }
+    phrases.sort_by_key(|phrase| (std::cmp::Reverse(phrase.len()), phrase.as_str()));
+
+    let alternatives: Vec<String> = phrases
+        .iter()
+        .map(|phrase| phrase.split(' ').map(regex::escape).collect::<Vec<_>>().join(r"\s+"))
+        .collect();
+
+    Some(Regex::new(&format!(r"(?i)\b(?:{})\b", alternatives.join("|"))).unwrap())
+}
@@ -0,0 +1,158 @@
+use std::sync::LazyLock;
+
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+
+/// Season, part and cour numbers written in a title, such as "2nd Season",
+/// "Season 2", "S2", "II", "第2期", "Part 2" or "Cour 2".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct SeasonInfo {
+    pub season: Option<u32>,
+    pub part: Option<u32>,
+    pub cour: Option<u32>,
+}
+
+struct Patterns {
+    part: Vec<Regex>,
+    cour: Vec<Regex>,
+    season: Vec<Regex>,
+}
+
+static PATTERNS: LazyLock<Patterns> = LazyLock::new(|| {
+    let compile = |patterns: &[&str]| patterns.iter().map(|p| Regex::new(p).unwrap()).collect();
+
+    Patterns {
+        part: compile(&[
+            r"(?i)\bpart\s*(\d+|[ivx]+)\b",
+            r"(?i)\b(\d+)(?:st|nd|rd|th)\s+part\b",
+            r"第\s*(\d+|[一二三四五六七八九十]+)\s*部",
+        ]),
+        cour: compile(&[
+            r"(?i)\bcour\s*(\d+)\b",
+            r"(?i)\b(\d+)(?:st|nd|rd|th)\s+cour\b",
+            r"第\s*(\d+|[一二三四五六七八九十]+)\s*クール",
+        ]),
+        season: compile(&[
+            r"(?i)\b(\d+)(?:st|nd|rd|th)\s+season\b",
+            r"(?i)\bseason\s*(\d+)\b",
+            r"(?i)\b(second|third|fourth|fifth|sixth|seventh|eighth|ninth|tenth)\s+season\b",
+            r"(?i)\bs(\d{1,2})(?:\s*e\d+)?\b",
+            r"第\s*(\d+|[一二三四五六七八九十]+)\s*(?:期|シーズン)",
+            // Upper case only, so words like "vi" in a title are left alone. A
+            // lone V or X is too often part of the title, as in "Hunter X
+            // Hunter", so it only counts after the word season
+            r"\b(II|III|IV|VI{1,3}|IX)\b",
+            r"(?i:\bseason)\s+(V|X)\b",
+        ]),
+    }
+});
+
+impl SeasonInfo {
+    /// Extracts the season, part and cour numbers from a title.
+    ///
+    /// Part and cour markers are read first and removed, so that "Part II"
+    /// is not also taken for a second season.
+    pub fn parse(title: &str) -> Self {
+        let mut text: String = title.nfkc().collect();
+
+        let part = take_number(&PATTERNS.part, &mut text);
+        let cour = take_number(&PATTERNS.cour, &mut text);
+        let season = take_number(&PATTERNS.season, &mut text);
+
+        Self { season, part, cour }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.season.is_none() && self.part.is_none() && self.cour.is_none()
+    }
+
+    /// Whether both titles give a different number for the season, the part
+    /// or the cour. A number missing on either side is not a conflict.
+    pub fn conflicts_with(&self, other: &SeasonInfo) -> bool {
+        let differ = |a: Option<u32>, b: Option<u32>| matches!((a, b), (Some(a), Some(b)) if a != b);
+
+        differ(self.season, other.season) || differ(self.part, other.part) || differ(self.cour, other.cour)
+    }
+}
+
+/// Reads the number of the first pattern that matches and blanks out every
+/// match of all of them
+fn take_number(patterns: &[Regex], text: &mut String) -> Option<u32> {
+    let mut number = None;
+
+    for pattern in patterns {
+        let replaced = pattern.replace_all(text, |captures: &Captures| {
+            if number.is_none() {
+                number = parse_number(&captures[1]);
+            }
+            " "
+        });
+        *text = replaced.into_owned();
+    }
+
+    number
+}
+
+fn parse_number(text: &str) -> Option<u32> {
+    if let Ok(number) = text.parse() {
+        return Some(number);
+    }
+
+    let lower = text.to_lowercase();
+    let ordinal = ["second", "third", "fourth", "fifth", "sixth", "seventh", "eighth", "ninth", "tenth"];
+    if let Some(position) = ordinal.iter().position(|o| *o == lower) {
+        return Some(position as u32 + 2);
+    }
+
+    let roman = ["i", "ii", "iii", "iv", "v", "vi", "vii", "viii", "ix", "x"];
+    if let Some(position) = roman.iter().position(|r| *r == lower) {
+        return Some(position as u32 + 1);
+    }
+
+    parse_kanji_number(text)
+}
+
+/// Parses kanji numerals from 1 to 99, such as 二, 十 or 二十三
+fn parse_kanji_number(text: &str) -> Option<u32> {
+    let digit = |c: char| "一二三四五六七八九".chars().position(|d| d == c).map(|p| p as u32 + 1);
+    let chars: Vec<char> = text.chars().collect();
+
+    match chars.as_slice() {
+        [c] if *c == '十' => Some(10),
+        [c] => digit(*c),
+        ['十', ones] => Some(10 + digit(*ones)?),
+        [tens, '十'] => Some(digit(*tens)? * 10),
+        [tens, '十', ones] => Some(digit(*tens)? * 10 + digit(*ones)?),
+        _ => None,
+    }
+}
+
+/// What the matcher does with a title pair whose season, part or cour
+/// numbers conflict, such as "Season 2" and "3rd Season".
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SeasonMismatch {
+    /// Compare the titles as if the numbers were not there.
+    Ignore,
+    /// Subtract the given amount from the pair's similarity.
+    Penalty(f64),
+    /// Never match the pair.
+    #[default]
+    Reject,
+}
+
+impl SeasonMismatch {
+    /// Adjusts the similarity of a title pair for their season numbers, or
+    /// returns `None` when the pair must not match.
+    pub(crate) fn apply(self, search: &SeasonInfo, candidate: &SeasonInfo, similarity: f64) -> Option<f64> {
+        if !search.conflicts_with(candidate) {
+            return Some(similarity);
+        }
+
+        match self {
+            SeasonMismatch::Ignore => Some(similarity),
+            SeasonMismatch::Penalty(penalty) => Some((similarity - penalty).max(0.0)),
+            SeasonMismatch::Reject => None,
+        }
+    }
+}
@@ -0,0 +1,303 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::r#match::jaro_winkler_distance;
+
+/// A string similarity metric used by the fuzzy tiers of the cascade.
+///
+/// Implementations return a score between 0 and 1, where 1 means the strings
+/// are identical. Any `Fn(&str, &str) -> f64` closure is also a metric.
+pub trait Similarity: Send + Sync {
+    fn similarity(&self, a: &str, b: &str) -> f64;
+
+    /// A short name for the metric, used in debug output.
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+}
+
+impl<F> Similarity for F
+where
+    F: Fn(&str, &str) -> f64 + Send + Sync,
+{
+    fn similarity(&self, a: &str, b: &str) -> f64 {
+        self(a, b)
+    }
+}
+
+/// Jaro-Winkler similarity, favouring strings that share a prefix.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JaroWinkler {
+    /// Prefix scaling factor, 0.1 when unset.
+    pub prefix_scale: Option<f64>,
+}
+
+impl Similarity for JaroWinkler {
+    fn similarity(&self, a: &str, b: &str) -> f64 {
+        jaro_winkler_distance(a, b, self.prefix_scale)
+    }
+
+    fn name(&self) -> &str {
+        "jaro_winkler"
+    }
+}
+
+/// Levenshtein distance scaled by the longer string's length.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Levenshtein;
+
+impl Similarity for Levenshtein {
+    fn similarity(&self, a: &str, b: &str) -> f64 {
+        let a: Vec<char> = a.chars().collect();
+        let b: Vec<char> = b.chars().collect();
+        normalize_distance(levenshtein(&a, &b), a.len(), b.len())
+    }
+
+    fn name(&self) -> &str {
+        "levenshtein"
+    }
+}
+
+/// Damerau-Levenshtein distance, counting adjacent transpositions as one
+/// edit, scaled by the longer string's length.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DamerauLevenshtein;
+
+impl Similarity for DamerauLevenshtein {
+    fn similarity(&self, a: &str, b: &str) -> f64 {
+        let a: Vec<char> = a.chars().collect();
+        let b: Vec<char> = b.chars().collect();
+        normalize_distance(damerau_levenshtein(&a, &b), a.len(), b.len())
+    }
+
+    fn name(&self) -> &str {
+        "damerau_levenshtein"
+    }
+}
+
+/// Normalized Levenshtein after sorting the whitespace-separated tokens of
+/// both strings, so word order does not matter.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokenSortRatio;
+
+impl Similarity for TokenSortRatio {
+    fn similarity(&self, a: &str, b: &str) -> f64 {
+        let mut a: Vec<&str> = a.split_whitespace().collect();
+        let mut b: Vec<&str> = b.split_whitespace().collect();
+        a.sort_unstable();
+        b.sort_unstable();
+        Levenshtein.similarity(&a.join(" "), &b.join(" "))
+    }
+
+    fn name(&self) -> &str {
+        "token_sort_ratio"
+    }
+}
+
+/// Compares the tokens both strings share against each string's full token
+/// set, so a title contained in a longer one scores highly.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokenSetRatio;
+
+impl Similarity for TokenSetRatio {
+    fn similarity(&self, a: &str, b: &str) -> f64 {
+        let a: HashSet<&str> = a.split_whitespace().collect();
+        let b: HashSet<&str> = b.split_whitespace().collect();
+
+        // With no tokens on one side, the shared tokens would equal that side
+        if a.is_empty() || b.is_empty() {
+            return if a.is_empty() && b.is_empty() { 1.0 } else { 0.0 };
+        }
+
+        let sorted = |tokens: Vec<&str>| {
+            let mut tokens = tokens;
+            tokens.sort_unstable();
+            tokens.join(" ")
+        };
+        let join = |head: &str, tail: &str| format!("{head} {tail}").trim().to_string();
+
+        let common = sorted(a.intersection(&b).copied().collect());
+        let only_a = sorted(a.difference(&b).copied().collect());
+        let only_b = sorted(b.difference(&a).copied().collect());
+
+        let with_a = join(&common, &only_a);
+        let with_b = join(&common, &only_b);
+
+        [
+            Levenshtein.similarity(&common, &with_a),
+            Levenshtein.similarity(&common, &with_b),
+            Levenshtein.similarity(&with_a, &with_b),
+        ]
+        .into_iter()
+        .fold(0.0, f64::max)
+    }
+
+    fn name(&self) -> &str {
+        "token_set_ratio"
+    }
+}
+
+/// Jaccard index of the character n-gram sets of both strings.
+#[derive(Debug, Clone, Copy)]
+pub struct NgramJaccard {
+    pub n: usize,
+}
+
+impl Default for NgramJaccard {
+    fn default() -> Self {
+        Self { n: 3 }
+    }
+}
+
+impl Similarity for NgramJaccard {
+    fn similarity(&self, a: &str, b: &str) -> f64 {
+        if a == b {
+            return 1.0;
+        }
+
+        let a: HashSet<Vec<char>> = ngrams(a, self.n).into_keys().collect();
+        let b: HashSet<Vec<char>> = ngrams(b, self.n).into_keys().collect();
+        let union = a.union(&b).count();
+
+        if union == 0 {
+            return 0.0;
+        }
+
+        a.intersection(&b).count() as f64 / union as f64
+    }
+
+    fn name(&self) -> &str {
+        "ngram_jaccard"
+    }
+}
+
+/// Cosine similarity of the character n-gram count vectors of both strings.
+#[derive(Debug, Clone, Copy)]
+pub struct NgramCosine {
+    pub n: usize,
+}
+
+impl Default for NgramCosine {
+    fn default() -> Self {
+        Self { n: 3 }
+    }
+}
+
+impl Similarity for NgramCosine {
+    fn similarity(&self, a: &str, b: &str) -> f64 {
+        if a == b {
+            return 1.0;
+        }
+
+        let a = ngrams(a, self.n);
+        let b = ngrams(b, self.n);
+
+        let dot: usize = a
+            .iter()
+            .filter_map(|(gram, count)| b.get(gram).map(|other| count * other))
+            .sum();
+        let norm = |counts: &HashMap<Vec<char>, usize>| {
+            counts.values().map(|&c| (c * c) as f64).sum::<f64>().sqrt()
+        };
+        let norms = norm(&a) * norm(&b);
+
+        if norms == 0.0 {
+            return 0.0;
+        }
+
+        (dot as f64 / norms).min(1.0)
+    }
+
+    fn name(&self) -> &str {
+        "ngram_cosine"
+    }
+}
+
+fn normalize_distance(distance: usize, len_a: usize, len_b: usize) -> f64 {
+    let longest = len_a.max(len_b);
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - distance as f64 / longest as f64
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            current[j + 1] = (previous[j] + cost)
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+/// Unrestricted Damerau-Levenshtein (Lowrance-Wagner)
+fn damerau_levenshtein(a: &[char], b: &[char]) -> usize {
+    let infinity = a.len() + b.len();
+    let width = b.len() + 2;
+    let mut d = vec![0; (a.len() + 2) * width];
+    let at = |i: usize, j: usize| i * width + j;
+
+    d[at(0, 0)] = infinity;
+    for i in 0..=a.len() {
+        d[at(i + 1, 0)] = infinity;
+        d[at(i + 1, 1)] = i;
+    }
+    for j in 0..=b.len() {
+        d[at(0, j + 1)] = infinity;
+        d[at(1, j + 1)] = j;
+    }
+
+    let mut last_row: HashMap<char, usize> = HashMap::new();
+
+    for i in 1..=a.len() {
+        let mut last_match_col = 0;
+        for j in 1..=b.len() {
+            let k = last_row.get(&b[j - 1]).copied().unwrap_or(0);
+            let l = last_match_col;
+            let cost = if a[i - 1] == b[j - 1] {
+                last_match_col = j;
+                0
+            } else {
+                1
+            };
+            let transposition = d[at(k, l)] + (i - k - 1) + 1 + (j - l - 1);
+            d[at(i + 1, j + 1)] = (d[at(i, j)] + cost)
+                .min(d[at(i + 1, j)] + 1)
+                .min(d[at(i, j + 1)] + 1)
+                .min(transposition);
+        }
+        last_row.insert(a[i - 1], i);
+    }
+
+    d[at(a.len() + 1, b.len() + 1)]
+}
+
+/// Counts the character n-grams of a string; strings shorter than `n` count
+/// as a single gram.
+pub(crate) fn ngrams(s: &str, n: usize) -> HashMap<Vec<char>, usize> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut counts = HashMap::new();
+
+    if chars.is_empty() {
+        return counts;
+    }
+
+    if chars.len() <= n.max(1) {
+        counts.insert(chars, 1);
+        return counts;
+    }
+
+    for gram in chars.windows(n.max(1)) {
+        *counts.entry(gram.to_vec()).or_insert(0) += 1;
+    }
+
+    counts
+}
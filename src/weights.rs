@@ -0,0 +1,62 @@
+use crate::r#match::TitleField;
+
+/// How much a fuzzy match on each candidate title field counts, and whether
+/// titles are only compared within the same language.
+///
+/// Fuzzy similarities are multiplied by the weight of the candidate field
+/// before thresholds apply, so a weight below 1.0 makes hits on that field
+/// harder to accept and lets other fields win ties. Weighted scores are capped
+/// at 1.0, and a field with a negative weight is never compared. Exact tiers
+/// are not affected, since identical titles match whatever field they are in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TitleWeights {
+    pub user_preferred: f64,
+    pub english: f64,
+    pub romaji: f64,
+    pub native: f64,
+    pub synonym: f64,
+    /// Only compare English with English, romaji with romaji and native with
+    /// native. User preferred titles and synonyms, whose language is unknown,
+    /// are still compared with every field.
+    pub same_language: bool,
+}
+
+impl Default for TitleWeights {
+    fn default() -> Self {
+        Self {
+            user_preferred: 1.0,
+            english: 1.0,
+            romaji: 1.0,
+            native: 1.0,
+            synonym: 1.0,
+            same_language: false,
+        }
+    }
+}
+
+impl TitleWeights {
+    /// The weight of a field.
+    pub fn weight(&self, field: TitleField) -> f64 {
+        match field {
+            TitleField::UserPreferred => self.user_preferred,
+            TitleField::English => self.english,
+            TitleField::Romaji => self.romaji,
+            TitleField::Native => self.native,
+            TitleField::Synonym => self.synonym,
+        }
+    }
+
+    /// The weight a fuzzy comparison between the two fields is scaled by, or
+    /// `None` when the pair is not compared at all. Scaled scores should be
+    /// capped at 1.0.
+    pub(crate) fn pair(&self, search_field: TitleField, candidate_field: TitleField) -> Option<f64> {
+        let untyped = |field| matches!(field, TitleField::UserPreferred | TitleField::Synonym);
+
+        if self.same_language && search_field != candidate_field && !untyped(search_field) && !untyped(candidate_field)
+        {
+            return None;
+        }
+
+        Some(self.weight(candidate_field)).filter(|&weight| weight >= 0.0)
+    }
+}
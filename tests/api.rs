@@ -0,0 +1,107 @@
+mod common;
+
+use am_algorithm::{
+    ExpectAnime, MatchMethod, clean_title, find_best_match, jaro_winkler_distance, match_title,
+    sanitize_title,
+};
+use common::{catalogue, id_of};
+
+#[test]
+fn exact_title_with_year_and_episodes() {
+    let catalogue = catalogue();
+    let search = ExpectAnime::from_string_title("Attack on Titan".into(), Some(2013), Some(25));
+
+    let m = find_best_match(&search, &catalogue, |anime| anime).unwrap();
+
+    assert_eq!(id_of(&m.result), 1);
+    assert_eq!(m.method, MatchMethod::ExactYearEpisodeRaw);
+    assert_eq!(m.similarity, 1.0);
+    assert_eq!(m.title.as_deref(), Some("Attack on Titan"));
+}
+
+#[test]
+fn normalized_title_with_year() {
+    let catalogue = catalogue();
+    let search = ExpectAnime::from_string_title("steins gate".into(), Some(2011), None);
+
+    let m = find_best_match(&search, &catalogue, |anime| anime).unwrap();
+
+    assert_eq!(id_of(&m.result), 4);
+    assert_eq!(m.method, MatchMethod::ExactYearNormalized);
+    assert_eq!(m.normalized.as_deref(), Some("steins gate"));
+}
+
+#[test]
+fn fuzzy_title_without_metadata() {
+    let catalogue = catalogue();
+
+    let m = match_title("Kimetsu no Yaibba", &catalogue).unwrap();
+
+    assert_eq!(id_of(&m.result), 2);
+    assert_eq!(m.method, MatchMethod::Loose);
+    assert!(m.similarity >= 0.8 && m.similarity < 1.0);
+}
+
+#[test]
+fn unrelated_title_does_not_match() {
+    let catalogue = catalogue();
+
+    assert!(match_title("Zzzzqqq", &catalogue).is_none());
+}
+
+#[test]
+fn empty_inputs_do_not_match() {
+    let catalogue = catalogue();
+
+    assert!(match_title("Naruto", &[]).is_none());
+    assert!(match_title("", &catalogue).is_none());
+}
+
+#[test]
+fn works_with_wrapper_types() {
+    struct Entry {
+        key: &'static str,
+        anime: ExpectAnime,
+    }
+    impl Clone for Entry {
+        fn clone(&self) -> Self {
+            Entry { key: self.key, anime: self.anime.clone() }
+        }
+    }
+
+    let entries: Vec<Entry> = catalogue()
+        .into_iter()
+        .map(|anime| Entry { key: "db", anime })
+        .collect();
+    let search = ExpectAnime::from_string_title("One Piece".into(), None, None);
+
+    let m = find_best_match(&search, &entries, |entry| &entry.anime).unwrap();
+
+    assert_eq!(m.result.key, "db");
+    assert_eq!(id_of(&m.result.anime), 7);
+}
+
+#[test]
+fn sanitize_title_strips_season_words() {
+    assert_eq!(
+        sanitize_title(Some("Boku no Hero Academia 2nd Season")).as_deref(),
+        Some("boku no hero academia 2nd")
+    );
+    assert_eq!(sanitize_title(Some("Yuu Yuu Hakusho")).as_deref(), Some("yu yu hakusho"));
+    assert_eq!(sanitize_title(None), None);
+}
+
+#[test]
+fn clean_title_collapses_punctuation() {
+    assert_eq!(clean_title(Some("Steins;Gate")).as_deref(), Some("Steins Gate"));
+    assert_eq!(clean_title(Some("  ＡＢＣ  ")).as_deref(), Some("ABC"));
+}
+
+#[test]
+fn jaro_winkler_bounds() {
+    assert_eq!(jaro_winkler_distance("naruto", "naruto", None), 1.0);
+    assert_eq!(jaro_winkler_distance("", "naruto", None), 0.0);
+    assert_eq!(jaro_winkler_distance("abc", "xyz", None), 0.0);
+    let score = jaro_winkler_distance("martha", "marhta", None);
+    assert!((score - 0.9611).abs() < 1e-4);
+}
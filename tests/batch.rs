@@ -0,0 +1,47 @@
+mod common;
+
+use am_algorithm::{ExpectAnime, MatchIndex, MatcherConfig, find_best_match_with, match_batch};
+use common::{catalogue, id_of};
+
+#[test]
+fn results_follow_input_order() {
+    let catalogue = catalogue();
+    let config = MatcherConfig::default().with_year_tolerance(1);
+    let index = MatchIndex::new(config.normalizer().normalize_catalogue(&catalogue, |anime| anime));
+
+    let searches: Vec<ExpectAnime> = [
+        ("Attack on Titan", Some(2013), Some(25)),
+        ("Zzzzqqq", None, None),
+        ("Kimetsu no Yaibba", Some(2019), None),
+        ("One Piece", Some(1999), Some(1100)),
+        ("steins gate", Some(2012), None),
+    ]
+    .iter()
+    .cycle()
+    .take(50)
+    .map(|&(title, year, episodes)| ExpectAnime::from_string_title(title.into(), year, episodes))
+    .collect();
+
+    let results = match_batch(&searches, &index, &config);
+    assert_eq!(results.len(), searches.len());
+
+    for (search, result) in searches.iter().zip(&results) {
+        let expected = find_best_match_with(search, &catalogue, |anime| anime, &config);
+        assert_eq!(
+            expected.map(|m| (id_of(&m.result), m.method, m.similarity)),
+            result.as_ref().map(|m| (id_of(&m.result), m.method, m.similarity)),
+        );
+    }
+
+    assert_eq!(id_of(&results[0].as_ref().unwrap().result), 1);
+    assert!(results[1].is_none());
+}
+
+#[test]
+fn empty_batch() {
+    let catalogue = catalogue();
+    let config = MatcherConfig::default();
+    let index = MatchIndex::new(config.normalizer().normalize_catalogue(&catalogue, |anime| anime));
+
+    assert!(match_batch(&[], &index, &config).is_empty());
+}
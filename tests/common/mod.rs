@@ -0,0 +1,64 @@
+#![allow(dead_code)]
+
+use am_algorithm::{AnimeTitle, ExpectAnime};
+
+pub fn anime(
+    id: i64,
+    english: &str,
+    romaji: &str,
+    native: &str,
+    year: i32,
+    episodes: i32,
+) -> ExpectAnime {
+    ExpectAnime {
+        id: Some(serde_json::json!(id)),
+        title: Some(AnimeTitle {
+            english: Some(english.into()),
+            romaji: Some(romaji.into()),
+            native: Some(native.into()),
+            user_preferred: Some(romaji.into()),
+            synonyms: Vec::new(),
+        }),
+        year: Some(year),
+        episodes: Some(episodes),
+        format: None,
+    }
+}
+
+pub fn catalogue() -> Vec<ExpectAnime> {
+    vec![
+        anime(1, "Attack on Titan", "Shingeki no Kyojin", "進撃の巨人", 2013, 25),
+        anime(2, "Demon Slayer: Kimetsu no Yaiba", "Kimetsu no Yaiba", "鬼滅の刃", 2019, 26),
+        anime(
+            3,
+            "My Hero Academia Season 2",
+            "Boku no Hero Academia 2nd Season",
+            "僕のヒーローアカデミア 第2期",
+            2017,
+            25,
+        ),
+        anime(4, "Steins;Gate", "Steins;Gate", "シュタインズ・ゲート", 2011, 24),
+        anime(
+            5,
+            "Fullmetal Alchemist: Brotherhood",
+            "Hagane no Renkinjutsushi: Fullmetal Alchemist",
+            "鋼の錬金術師 FULLMETAL ALCHEMIST",
+            2009,
+            64,
+        ),
+        anime(6, "Naruto Shippuden", "Naruto: Shippuuden", "ナルト 疾風伝", 2007, 500),
+        anime(7, "One Piece", "One Piece", "ワンピース", 1999, 1000),
+        anime(
+            8,
+            "Bleach: Thousand-Year Blood War",
+            "Bleach: Sennen Kessen-hen",
+            "BLEACH 千年血戦篇",
+            2022,
+            13,
+        ),
+    ]
+}
+
+pub fn id_of(anime: &ExpectAnime) -> i64 {
+    anime.id.as_ref().and_then(|id| id.as_i64()).unwrap()
+}
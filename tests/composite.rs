@@ -0,0 +1,87 @@
+mod common;
+
+use am_algorithm::{
+    CompositeScoring, ExpectAnime, MatchMethod, MatcherConfig, ScoringMode, find_best_match,
+    find_best_match_with, find_matches_with,
+};
+use common::{anime, id_of};
+
+fn fruits_basket() -> Vec<ExpectAnime> {
+    vec![
+        anime(1, "Fruits Basket", "Fruits Basket", "フルーツバスケット", 2001, 26),
+        anime(2, "Fruits Basket 2019", "Fruits Basket 2019", "フルーツバスケット", 2019, 25),
+    ]
+}
+
+fn composite() -> MatcherConfig {
+    MatcherConfig::new().with_scoring(ScoringMode::Composite(CompositeScoring::default()))
+}
+
+#[test]
+fn year_and_episodes_outweigh_a_slightly_better_title() {
+    let catalogue = fruits_basket();
+    let search = ExpectAnime::from_string_title("Fruits Basket".into(), Some(2019), Some(25));
+
+    let cascade = find_best_match(&search, &catalogue, |anime| anime).unwrap();
+    assert_eq!(id_of(&cascade.result), 1);
+
+    let m = find_best_match_with(&search, &catalogue, |anime| anime, &composite()).unwrap();
+    assert_eq!(id_of(&m.result), 2);
+    assert_eq!(m.method, MatchMethod::Composite);
+
+    let breakdown = m.breakdown.unwrap();
+    assert!(breakdown.title < 1.0);
+    assert_eq!(breakdown.year, Some(1.0));
+    assert_eq!(breakdown.episodes, Some(1.0));
+    assert_eq!(breakdown.composite, m.similarity);
+}
+
+#[test]
+fn missing_metadata_is_left_out_of_the_score() {
+    let catalogue = fruits_basket();
+    let search = ExpectAnime::from_string_title("Fruits Basket".into(), None, None);
+
+    let m = find_best_match_with(&search, &catalogue, |anime| anime, &composite()).unwrap();
+    let breakdown = m.breakdown.unwrap();
+
+    assert_eq!(id_of(&m.result), 1);
+    assert_eq!(breakdown.year, None);
+    assert_eq!(breakdown.episodes, None);
+    assert_eq!(breakdown.composite, 1.0);
+    assert_eq!(m.year, None);
+}
+
+#[test]
+fn breakdown_uses_the_configured_weights() {
+    let catalogue = vec![anime(1, "Bleach", "Bleach", "BLEACH", 2004, 366)];
+    let search = ExpectAnime::from_string_title("Bleach".into(), Some(2005), Some(183));
+    let scoring = CompositeScoring {
+        title_weight: 2.0,
+        year_weight: 1.0,
+        episodes_weight: 1.0,
+        format_weight: 1.0,
+        year_scale: 4.0,
+        threshold: 0.0,
+    };
+    let config = MatcherConfig::new().with_scoring(ScoringMode::Composite(scoring));
+
+    let m = find_best_match_with(&search, &catalogue, |anime| anime, &config).unwrap();
+    let breakdown = m.breakdown.unwrap();
+
+    assert_eq!(breakdown.title, 1.0);
+    assert_eq!(breakdown.year, Some(0.75));
+    assert_eq!(breakdown.episodes, Some(0.5));
+    assert!((breakdown.composite - (2.0 + 0.75 + 0.5) / 4.0).abs() < 1e-12);
+}
+
+#[test]
+fn ranked_by_composite_score_above_threshold() {
+    let mut catalogue = fruits_basket();
+    catalogue.push(anime(3, "One Piece", "One Piece", "ワンピース", 1999, 1000));
+    let search = ExpectAnime::from_string_title("Fruits Basket".into(), Some(2019), Some(25));
+
+    let ranked = find_matches_with(&search, &catalogue, 10, |anime| anime, &composite());
+
+    assert_eq!(ranked.iter().map(|m| id_of(&m.result)).collect::<Vec<_>>(), vec![2, 1]);
+    assert!(ranked[0].similarity >= ranked[1].similarity);
+}
@@ -0,0 +1,119 @@
+mod common;
+
+use am_algorithm::{
+    AnimeTitle, ExpectAnime, MatchMethod, MatcherConfig, find_best_match, find_best_match_with, find_matches_with,
+};
+use common::{anime, catalogue, id_of};
+
+#[test]
+fn default_config_reproduces_the_original_matcher() {
+    let catalogue = catalogue();
+
+    #[rustfmt::skip]
+    let cases = [
+        ("Attack on Titan", Some(2013), Some(25), Some((1, MatchMethod::ExactYearEpisodeRaw, 1.0, Some("Attack on Titan"), None))),
+        ("steins gate", Some(2011), None, Some((4, MatchMethod::ExactYearNormalized, 1.0, None, Some("steins gate")))),
+        ("Kimetsu no Yaibba", None, None, Some((2, MatchMethod::Loose, 0.9882352941176471, None, Some("kimetsu no yaibba")))),
+        ("Bleach Blood War", None, None, Some((8, MatchMethod::Loose, 0.8566666666666667, None, Some("bleach blood war")))),
+        ("Zzzzqqq", None, None, None),
+    ];
+
+    for (title, year, episodes, expected) in cases {
+        let search = ExpectAnime::from_string_title(title.into(), year, episodes);
+        let actual = find_best_match_with(&search, &catalogue, |anime| anime, &MatcherConfig::default());
+
+        assert_eq!(
+            actual.map(|m| (id_of(&m.result), m.method, m.similarity, m.title, m.normalized)),
+            expected.map(|(id, method, similarity, title, normalized)| {
+                (id, method, similarity, title.map(String::from), normalized.map(String::from))
+            }),
+            "{title}"
+        );
+    }
+}
+
+#[test]
+fn fuzzy_ties_keep_the_first_search_title() {
+    // Each search title is as close to one of the candidate's titles, so the
+    // match must come from the search title that is tried first
+    let catalogue = vec![anime(1, "Monster", "Gintama", "銀魂", 2004, 74)];
+    let search = ExpectAnime {
+        id: None,
+        title: Some(AnimeTitle {
+            english: Some("Gintaka".into()),
+            romaji: Some("Monstor".into()),
+            native: None,
+            user_preferred: None,
+            synonyms: Vec::new(),
+        }),
+        year: None,
+        episodes: None,
+        format: None,
+    };
+
+    let m = find_best_match(&search, &catalogue, |anime| anime).unwrap();
+    assert_eq!(id_of(&m.result), 1);
+    assert_eq!(m.method, MatchMethod::Loose);
+    assert_eq!(m.similarity, 0.9428571428571428);
+    assert_eq!(m.title, None);
+    assert_eq!(m.normalized.as_deref(), Some("gintaka"));
+    assert_eq!(m.candidate_title.as_deref(), Some("Gintama"));
+}
+
+#[test]
+fn default_config_lists_every_tier() {
+    let config = MatcherConfig::new();
+
+    assert_eq!(config.tiers(), &MatchMethod::ALL);
+    assert_eq!(config.threshold(MatchMethod::LooseYear), 0.8);
+    assert_eq!(config.threshold(MatchMethod::Loose), 0.8);
+    assert_eq!(config.threshold(MatchMethod::LastResort), 0.7);
+    assert_eq!(config.threshold(MatchMethod::NullMethod), 0.6);
+    assert_eq!(config.threshold(MatchMethod::Exact), 1.0);
+}
+
+#[test]
+fn raising_a_threshold_rejects_weaker_matches() {
+    let catalogue = catalogue();
+    let search = ExpectAnime::from_string_title("Kimetsu no Yaibba".into(), None, None);
+
+    let strict = MatcherConfig::new()
+        .with_loose_threshold(0.99)
+        .with_last_resort_threshold(0.99)
+        .with_null_method_threshold(0.99);
+
+    assert!(find_best_match_with(&search, &catalogue, |anime| anime, &strict).is_none());
+}
+
+#[test]
+fn disabled_tiers_are_skipped() {
+    let catalogue = catalogue();
+    let search = ExpectAnime::from_string_title("Attack on Titan".into(), Some(2013), Some(25));
+    let config = MatcherConfig::new()
+        .without_tier(MatchMethod::ExactYearEpisodeRaw)
+        .without_tier(MatchMethod::ExactYearEpisodeNormalized);
+
+    let m = find_best_match_with(&search, &catalogue, |anime| anime, &config).unwrap();
+
+    assert_eq!(m.method, MatchMethod::ExactYearRaw);
+    assert_eq!(config.tiers().len(), 8);
+}
+
+#[test]
+fn tiers_run_in_the_configured_order() {
+    let catalogue = catalogue();
+    let search = ExpectAnime::from_string_title("Attack on Titan".into(), Some(2013), Some(25));
+    let config = MatcherConfig::new().with_tiers([
+        MatchMethod::Loose,
+        MatchMethod::Exact,
+        MatchMethod::Loose,
+    ]);
+
+    assert_eq!(config.tiers(), &[MatchMethod::Loose, MatchMethod::Exact]);
+
+    let m = find_best_match_with(&search, &catalogue, |anime| anime, &config).unwrap();
+    assert_eq!(m.method, MatchMethod::Loose);
+
+    let ranked = find_matches_with(&search, &catalogue, 5, |anime| anime, &config);
+    assert!(ranked.iter().all(|m| m.method == MatchMethod::Loose));
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use am_algorithm::{
+    AnimeTitle, EpisodeMatch, EpisodeTolerance, ExpectAnime, MatchMethod, MatcherConfig,
+    find_best_match, find_best_match_with,
+};
+use common::catalogue;
+
+fn one_piece(episodes: Option<i32>) -> Vec<ExpectAnime> {
+    vec![ExpectAnime {
+        id: Some(serde_json::json!(7)),
+        title: Some(AnimeTitle {
+            english: Some("One Piece".into()),
+            romaji: Some("One Piece".into()),
+            native: Some("ワンピース".into()),
+            user_preferred: Some("One Piece".into()),
+            synonyms: Vec::new(),
+        }),
+        year: Some(1999),
+        episodes,
+        format: None,
+    }]
+}
+
+fn search(episodes: i32) -> ExpectAnime {
+    ExpectAnime::from_string_title("One Piece".into(), Some(1999), Some(episodes))
+}
+
+fn method_with(tolerance: EpisodeTolerance, search: &ExpectAnime, catalogue: &[ExpectAnime]) -> MatchMethod {
+    let config = MatcherConfig::new().with_episode_tolerance(tolerance);
+    find_best_match_with(search, catalogue, |anime| anime, &config).unwrap().method
+}
+
+#[test]
+fn exact_count_is_required_by_default() {
+    let catalogue = catalogue();
+
+    let m = find_best_match(&search(1000), &catalogue, |anime| anime).unwrap();
+    assert_eq!(m.method, MatchMethod::ExactYearEpisodeRaw);
+    assert_eq!(m.episode_match, Some(EpisodeMatch::Exact));
+
+    let m = find_best_match(&search(1071), &catalogue, |anime| anime).unwrap();
+    assert_eq!(m.method, MatchMethod::ExactYearRaw);
+    assert_eq!(m.episode_match, None);
+}
+
+#[test]
+fn absolute_tolerance() {
+    let catalogue = one_piece(Some(1000));
+    let tolerance = EpisodeTolerance {
+        absolute: 2,
+        ..Default::default()
+    };
+
+    assert_eq!(method_with(tolerance, &search(1002), &catalogue), MatchMethod::ExactYearEpisodeRaw);
+    assert_eq!(method_with(tolerance, &search(1003), &catalogue), MatchMethod::ExactYearRaw);
+}
+
+#[test]
+fn relative_tolerance_scales_with_the_larger_count() {
+    let catalogue = one_piece(Some(1000));
+    let tolerance = EpisodeTolerance {
+        relative: 0.1,
+        ..Default::default()
+    };
+    let config = MatcherConfig::new().with_episode_tolerance(tolerance);
+
+    let m = find_best_match_with(&search(1100), &catalogue, |anime| anime, &config).unwrap();
+    assert_eq!(m.method, MatchMethod::ExactYearEpisodeRaw);
+    assert_eq!(m.episode_match, Some(EpisodeMatch::Within(100)));
+
+    assert_eq!(method_with(tolerance, &search(1112), &catalogue), MatchMethod::ExactYearRaw);
+}
+
+#[test]
+fn unknown_counts_match_when_enabled() {
+    let tolerance = EpisodeTolerance {
+        unknown_matches: true,
+        ..Default::default()
+    };
+
+    for catalogue in [one_piece(None), one_piece(Some(0))] {
+        assert_eq!(method_with(EpisodeTolerance::default(), &search(1071), &catalogue), MatchMethod::ExactYearRaw);
+
+        let config = MatcherConfig::new().with_episode_tolerance(tolerance);
+        let m = find_best_match_with(&search(1071), &catalogue, |anime| anime, &config).unwrap();
+        assert_eq!(m.method, MatchMethod::ExactYearEpisodeRaw);
+        assert_eq!(m.episode_match, Some(EpisodeMatch::Unknown));
+    }
+
+    // An unknown searched count still skips the episode tiers
+    let catalogue = one_piece(None);
+    assert_eq!(method_with(tolerance, &search(0), &catalogue), MatchMethod::ExactYearRaw);
+}
+
+#[test]
+fn search_as_lower_bound_for_ongoing_series() {
+    let catalogue = one_piece(Some(1000));
+    let tolerance = EpisodeTolerance {
+        search_is_lower_bound: true,
+        ..Default::default()
+    };
+    let config = MatcherConfig::new().with_episode_tolerance(tolerance);
+
+    let m = find_best_match_with(&search(900), &catalogue, |anime| anime, &config).unwrap();
+    assert_eq!(m.method, MatchMethod::ExactYearEpisodeRaw);
+    assert_eq!(m.episode_match, Some(EpisodeMatch::Ongoing));
+
+    assert_eq!(method_with(tolerance, &search(1001), &catalogue), MatchMethod::ExactYearRaw);
+}
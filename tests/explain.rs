@@ -0,0 +1,121 @@
+mod common;
+
+use am_algorithm::{
+    CompositeScoring, ExpectAnime, Ineligible, MatchMethod, MatcherConfig, MediaFormat, ScoringMode, SeasonInfo,
+    explain, find_best_match_with,
+};
+use common::{catalogue, id_of};
+
+#[test]
+fn winner_agrees_with_find_best_match() {
+    let catalogue = catalogue();
+    let composite = MatcherConfig::new().with_scoring(ScoringMode::Composite(CompositeScoring::default()));
+
+    for config in [MatcherConfig::default(), composite] {
+        for (title, year, episodes) in [
+            ("Attack on Titan", Some(2013), Some(25)),
+            ("steins gate", Some(2011), None),
+            ("Kimetsu no Yaibba", None, None),
+            ("Zzzzqqq", None, None),
+        ] {
+            let search = ExpectAnime::from_string_title(title.into(), year, episodes);
+            let expected = find_best_match_with(&search, &catalogue, |anime| anime, &config);
+            let explanation = explain(&search, &catalogue, |anime| anime, &config);
+
+            assert_eq!(
+                expected.map(|m| (id_of(&m.result), m.method, m.similarity)),
+                explanation
+                    .winner
+                    .map(|w| (id_of(&catalogue[w.index]), w.method, w.similarity)),
+                "{title}"
+            );
+        }
+    }
+}
+
+#[test]
+fn records_every_tier_tried() {
+    let catalogue = catalogue();
+    let search = ExpectAnime::from_string_title("Kimetsu no Yaibba".into(), Some(2019), None);
+
+    let explanation = explain(&search, &catalogue, |anime| anime, &MatcherConfig::default());
+    let methods: Vec<MatchMethod> = explanation.tiers.iter().map(|t| t.method).collect();
+
+    // The episode tiers never apply without a searched episode count
+    assert_eq!(methods, MatchMethod::ALL[..7].to_vec());
+    assert!(explanation.tiers[0].candidates.iter().all(|c| c.ineligible.is_some()));
+    assert_eq!(explanation.tiers[0].candidates[1].ineligible, Some(Ineligible::Episodes));
+    assert_eq!(explanation.search.normalized_titles, vec!["kimetsu no yaibba"]);
+
+    let loose_year = explanation.tiers.last().unwrap();
+    assert_eq!(loose_year.best, Some(1));
+    assert_eq!(loose_year.threshold, 0.8);
+
+    let candidate = &loose_year.candidates[1];
+    assert_eq!(candidate.id, Some(serde_json::json!(2)));
+    assert_eq!(candidate.pairs.len(), 4);
+    let pair = candidate
+        .pairs
+        .iter()
+        .find(|p| p.candidate_title == "Kimetsu no Yaiba")
+        .unwrap();
+    assert_eq!(
+        pair.normalized,
+        Some(("kimetsu no yaibba".to_string(), "kimetsu no yaiba".to_string()))
+    );
+    assert_eq!(candidate.similarity, Some(pair.score));
+
+    let other_year = &loose_year.candidates[0];
+    assert_eq!(other_year.ineligible, Some(Ineligible::Year));
+    assert!(other_year.pairs.is_empty());
+}
+
+#[test]
+fn no_match_traces_all_tiers() {
+    let catalogue = catalogue();
+    let search = ExpectAnime::from_string_title("Zzzzqqq".into(), None, None);
+
+    let explanation = explain(&search, &catalogue, |anime| anime, &MatcherConfig::default());
+
+    assert!(explanation.winner.is_none());
+    assert_eq!(explanation.tiers.len(), MatchMethod::ALL.len());
+    let null_method = explanation.tiers.last().unwrap();
+    assert!(null_method.candidates.iter().all(|c| c.similarity.is_none() && !c.pairs.is_empty()));
+}
+
+#[test]
+fn traces_the_searched_seasons_and_format() {
+    let catalogue = catalogue();
+    let mut search = ExpectAnime::from_string_title("My Hero Academia Season 2".into(), None, None);
+    search.format = Some(MediaFormat::Tv);
+
+    let explanation = explain(&search, &catalogue, |anime| anime, &MatcherConfig::default());
+    assert_eq!(explanation.search.seasons, [SeasonInfo::parse("My Hero Academia Season 2")]);
+    assert_eq!(explanation.search.seasons[0].season, Some(2));
+    assert_eq!(explanation.search.format, Some(MediaFormat::Tv));
+
+    let mut search = ExpectAnime::from_string_title(String::new(), Some(2013), None);
+    search.title = None;
+    search.format = Some(MediaFormat::Movie);
+
+    let explanation = explain(&search, &catalogue, |anime| anime, &MatcherConfig::default());
+    assert!(explanation.search.titles.is_empty() && explanation.search.seasons.is_empty());
+    assert_eq!(explanation.search.year, Some(2013));
+    assert_eq!(explanation.search.format, Some(MediaFormat::Movie));
+    assert!(explanation.tiers.is_empty());
+}
+
+#[test]
+fn serializes_to_json() {
+    let catalogue = catalogue();
+    let search = ExpectAnime::from_string_title("Steins;Gate".into(), Some(2011), Some(24));
+
+    let explanation = explain(&search, &catalogue, |anime| anime, &MatcherConfig::default());
+    let json = serde_json::to_value(&explanation).unwrap();
+
+    assert_eq!(json["winner"]["method"], "exact_year_episode_raw");
+    assert_eq!(json["winner"]["index"], 3);
+    assert_eq!(json["tiers"][0]["method"], "exact_year_episode_raw");
+    assert_eq!(json["tiers"][0]["candidates"][0]["ineligible"], "year");
+    assert_eq!(json["search"]["year"], 2011);
+}
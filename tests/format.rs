@@ -0,0 +1,180 @@
+mod common;
+
+use am_algorithm::{
+    AnimeTitle, CompositeScoring, ExpectAnime, FormatMismatch, Ineligible, MatchMethod, MatcherConfig, MediaFormat,
+    ScoringMode, explain, find_best_match, find_best_match_with, find_matches,
+};
+use common::id_of;
+
+fn entry(id: i64, title: &str, format: Option<MediaFormat>) -> ExpectAnime {
+    ExpectAnime {
+        id: Some(serde_json::json!(id)),
+        title: Some(AnimeTitle {
+            english: Some(title.into()),
+            ..AnimeTitle::default()
+        }),
+        year: Some(2018),
+        episodes: None,
+        format,
+    }
+}
+
+fn violet() -> Vec<ExpectAnime> {
+    vec![
+        entry(1, "Violet Evergarden", Some(MediaFormat::Tv)),
+        entry(2, "Violet Evergarden", Some(MediaFormat::Movie)),
+        entry(3, "Violet Evergarden", Some(MediaFormat::Special)),
+    ]
+}
+
+fn search(title: &str, format: Option<MediaFormat>) -> ExpectAnime {
+    ExpectAnime {
+        format,
+        ..ExpectAnime::from_string_title(title.into(), None, None)
+    }
+}
+
+#[test]
+fn prefers_the_searched_format() {
+    let catalogue = violet();
+
+    let result = find_best_match(&search("Violet Evergarden", None), &catalogue, |anime| anime).unwrap();
+    assert_eq!(id_of(&result.result), 1);
+    assert_eq!(result.format_match, None);
+
+    let result = find_best_match(&search("Violet Evergarden", Some(MediaFormat::Movie)), &catalogue, |anime| anime)
+        .unwrap();
+    assert_eq!(id_of(&result.result), 2);
+    assert_eq!(result.method, MatchMethod::Exact);
+    assert_eq!(result.format_match, Some(true));
+
+    let ranked = find_matches(&search("Violet Evergarden", Some(MediaFormat::Special)), &catalogue, 3, |anime| anime);
+    assert_eq!(ranked.iter().map(|m| id_of(&m.result)).collect::<Vec<_>>(), [3, 1, 2]);
+}
+
+#[test]
+fn prefers_the_searched_format_over_similarity() {
+    let catalogue = vec![
+        entry(1, "Violet Evergarden", Some(MediaFormat::Tv)),
+        entry(2, "Violet Evergarden Movie", Some(MediaFormat::Movie)),
+    ];
+
+    let result = find_best_match(&search("Violet Evergardn", Some(MediaFormat::Movie)), &catalogue, |anime| anime)
+        .unwrap();
+    assert_eq!(id_of(&result.result), 2);
+}
+
+#[test]
+fn unknown_formats_are_not_mismatches() {
+    let catalogue = vec![entry(1, "Violet Evergarden", None)];
+    let config = MatcherConfig::default().with_format_mismatch(FormatMismatch::Reject);
+
+    let result =
+        find_best_match_with(&search("Violet Evergarden", Some(MediaFormat::Movie)), &catalogue, |anime| anime, &config)
+            .unwrap();
+    assert_eq!(result.format_match, None);
+}
+
+#[test]
+fn mismatches_can_be_rejected() {
+    let catalogue = vec![entry(1, "Violet Evergarden", Some(MediaFormat::Tv))];
+    let search = search("Violet Evergarden", Some(MediaFormat::Movie));
+
+    let result = find_best_match(&search, &catalogue, |anime| anime).unwrap();
+    assert_eq!(result.format_match, Some(false));
+
+    let config = MatcherConfig::default().with_format_mismatch(FormatMismatch::Reject);
+    assert!(find_best_match_with(&search, &catalogue, |anime| anime, &config).is_none());
+
+    let explanation = explain(&search, &catalogue, |anime| anime, &config);
+    assert!(explanation.winner.is_none());
+    assert!(explanation.tiers.iter().all(|tier| tier.candidates[0].ineligible.is_some()));
+    let exact = explanation.tiers.iter().find(|tier| tier.method == MatchMethod::Exact).unwrap();
+    assert_eq!(exact.candidates[0].ineligible, Some(Ineligible::Format));
+}
+
+#[test]
+fn mismatches_can_be_penalized() {
+    let catalogue = vec![entry(1, "Violet Evergarden", Some(MediaFormat::Tv))];
+    let config = MatcherConfig::default().with_format_mismatch(FormatMismatch::Penalty(0.1));
+
+    let unpenalized = find_best_match(&search("Violet Evergardn", None), &catalogue, |anime| anime).unwrap();
+    let penalized =
+        find_best_match_with(&search("Violet Evergardn", Some(MediaFormat::Movie)), &catalogue, |anime| anime, &config)
+            .unwrap();
+    assert_eq!(penalized.method, MatchMethod::Loose);
+    assert!((unpenalized.similarity - penalized.similarity - 0.1).abs() < 1e-9);
+
+    let exact =
+        find_best_match_with(&search("Violet Evergarden", Some(MediaFormat::Movie)), &catalogue, |anime| anime, &config)
+            .unwrap();
+    assert_eq!(exact.method, MatchMethod::Exact);
+    assert!((exact.similarity - 0.9).abs() < 1e-9);
+}
+
+#[test]
+fn explain_prefers_the_searched_format() {
+    let catalogue = violet();
+    let explanation = explain(
+        &search("Violet Evergarden", Some(MediaFormat::Movie)),
+        &catalogue,
+        |anime| anime,
+        &MatcherConfig::default(),
+    );
+
+    assert_eq!(explanation.winner.unwrap().index, 1);
+    let tier = explanation.tiers.last().unwrap();
+    assert_eq!(tier.candidates[0].format_match, Some(false));
+    assert_eq!(tier.candidates[1].format_match, Some(true));
+}
+
+#[test]
+fn composite_scores_the_format() {
+    let catalogue = violet();
+    let config = MatcherConfig::default().with_scoring(ScoringMode::Composite(CompositeScoring::default()));
+
+    let result =
+        find_best_match_with(&search("Violet Evergarden", Some(MediaFormat::Movie)), &catalogue, |anime| anime, &config)
+            .unwrap();
+    assert_eq!(id_of(&result.result), 2);
+    assert_eq!(result.breakdown.unwrap().format, Some(1.0));
+    assert_eq!(result.format_match, Some(true));
+}
+
+#[test]
+fn composite_rejects_mismatches_when_asked() {
+    let catalogue = vec![entry(1, "Violet Evergarden", Some(MediaFormat::Tv))];
+    let search = search("Violet Evergarden", Some(MediaFormat::Movie));
+    let composite = MatcherConfig::default().with_scoring(ScoringMode::Composite(CompositeScoring::default()));
+
+    assert!(find_best_match_with(&search, &catalogue, |anime| anime, &composite).is_some());
+
+    let config = composite.with_format_mismatch(FormatMismatch::Reject);
+    assert!(find_best_match_with(&search, &catalogue, |anime| anime, &config).is_none());
+
+    let explanation = explain(&search, &catalogue, |anime| anime, &config);
+    assert!(explanation.winner.is_none());
+    assert_eq!(explanation.tiers[0].candidates[0].ineligible, Some(Ineligible::Format));
+    assert_eq!(explanation.tiers[0].candidates[0].similarity, None);
+}
+
+#[test]
+fn formats_use_anilist_names() {
+    let json = serde_json::to_value(entry(1, "Violet Evergarden", Some(MediaFormat::TvShort))).unwrap();
+    assert_eq!(json["format"], "TV_SHORT");
+
+    let anime: ExpectAnime = serde_json::from_value(serde_json::json!({
+        "id": 1,
+        "title": null,
+        "year": null,
+        "episodes": null,
+        "format": "OVA",
+    }))
+    .unwrap();
+    assert_eq!(anime.format, Some(MediaFormat::Ova));
+
+    let anime: ExpectAnime =
+        serde_json::from_value(serde_json::json!({"id": 1, "title": null, "year": null, "episodes": null})).unwrap();
+    assert_eq!(anime.format, None);
+    assert!(serde_json::to_value(&anime).unwrap().get("format").is_none());
+}
@@ -0,0 +1,139 @@
+mod common;
+
+use am_algorithm::{
+    AnimeTitle, ExpectAnime, MatchIndex, MatchMethod, MatchResult, MatcherConfig, Normalizer,
+    find_best_match_with, find_matches_with,
+};
+use common::{catalogue, id_of};
+use proptest::prelude::*;
+
+type Summary = (i64, MatchMethod, f64, Option<String>, Option<String>, Option<String>);
+
+fn summary(m: &MatchResult<ExpectAnime>) -> Summary {
+    (
+        id_of(&m.result),
+        m.method,
+        m.similarity,
+        m.title.clone(),
+        m.normalized.clone(),
+        m.candidate_title.clone(),
+    )
+}
+
+fn assert_agrees(search: &ExpectAnime, catalogue: &[ExpectAnime], config: &MatcherConfig) {
+    let index = MatchIndex::new(config.normalizer().normalize_catalogue(catalogue, |anime| anime));
+
+    let expected = find_best_match_with(search, catalogue, |anime| anime, config);
+    let actual = index.find_best_match(search, config);
+    assert_eq!(expected.as_ref().map(summary), actual.as_ref().map(summary), "{search:?}");
+
+    let expected = find_matches_with(search, catalogue, catalogue.len(), |anime| anime, config);
+    let actual = index.find_matches(search, catalogue.len(), config);
+    assert_eq!(
+        expected.iter().map(summary).collect::<Vec<_>>(),
+        actual.iter().map(summary).collect::<Vec<_>>(),
+        "{search:?}"
+    );
+}
+
+#[test]
+fn indexes_every_candidate() {
+    let catalogue = catalogue();
+    let index = MatchIndex::new(Normalizer::new().normalize_catalogue(&catalogue, |anime| anime));
+
+    assert_eq!(index.len(), catalogue.len());
+    assert!(!index.is_empty());
+    assert_eq!(index.titles(6).unwrap().normalized[0], "one piece");
+    assert!(index.titles(catalogue.len()).is_none());
+}
+
+#[test]
+fn agrees_with_ad_hoc_search() {
+    let catalogue = catalogue();
+    let configs = [
+        MatcherConfig::default(),
+        MatcherConfig::default().with_year_tolerance(1),
+        MatcherConfig::default().with_tiers([MatchMethod::Loose, MatchMethod::ExactNormalized]),
+    ];
+
+    for config in &configs {
+        for (title, year, episodes) in [
+            ("Attack on Titan", Some(2013), Some(25)),
+            ("Attack on Titan", Some(2014), Some(25)),
+            ("shingeki no kyojin", None, None),
+            ("Steins;Gate", Some(2011), Some(24)),
+            ("Kimetsu no Yaibba", Some(2019), None),
+            ("One Piece", Some(1999), Some(1100)),
+            ("Zzzzqqq", None, None),
+        ] {
+            assert_agrees(&ExpectAnime::from_string_title(title.into(), year, episodes), &catalogue, config);
+        }
+    }
+}
+
+#[test]
+fn duplicate_titles_keep_catalogue_order() {
+    let mut catalogue = catalogue();
+    let mut rerun = catalogue[6].clone();
+    rerun.id = Some(serde_json::json!(9));
+    rerun.year = Some(2023);
+    catalogue.push(rerun);
+
+    let config = MatcherConfig::default();
+    let index = MatchIndex::new(config.normalizer().normalize_catalogue(&catalogue, |anime| anime));
+
+    let search = ExpectAnime::from_string_title("One Piece".into(), None, None);
+    assert_eq!(id_of(&index.find_best_match(&search, &config).unwrap().result), 7);
+
+    let search = ExpectAnime::from_string_title("One Piece".into(), Some(2023), None);
+    assert_eq!(id_of(&index.find_best_match(&search, &config).unwrap().result), 9);
+
+    assert_agrees(&search, &catalogue, &config);
+}
+
+const WORDS: [&str; 12] = [
+    "attack", "titan", "no", "kyojin", "shingeki", "naruto", "shippuuden", "season", "2nd", "one", "piece", "gate",
+];
+
+fn title() -> impl Strategy<Value = String> {
+    prop::collection::vec(prop::sample::select(&WORDS[..]), 1..4).prop_map(|words| words.join(" "))
+}
+
+fn anime() -> impl Strategy<Value = ExpectAnime> {
+    (
+        title(),
+        prop::option::of(title()),
+        prop::option::of(2000..2004),
+        prop::option::of(0..3),
+    )
+        .prop_map(|(english, romaji, year, episodes)| ExpectAnime {
+            id: None,
+            title: Some(AnimeTitle {
+                english: Some(english),
+                romaji,
+                native: None,
+                user_preferred: None,
+                synonyms: Vec::new(),
+            }),
+            year,
+            episodes,
+            format: None,
+        })
+}
+
+proptest! {
+    #[test]
+    fn agrees_on_random_catalogues(
+        candidates in prop::collection::vec(anime(), 0..12),
+        search in anime(),
+        year_tolerance in 0u32..3,
+    ) {
+        let catalogue: Vec<ExpectAnime> = candidates
+            .into_iter()
+            .enumerate()
+            .map(|(id, anime)| ExpectAnime { id: Some(serde_json::json!(id)), ..anime })
+            .collect();
+
+        assert_agrees(&search, &catalogue, &MatcherConfig::default().with_year_tolerance(year_tolerance));
+    }
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use std::collections::HashMap;
+
+use am_algorithm::{
+    ExpectAnime, MatchIndex, MatcherConfig, TitleField, explain, find_best_match, match_batch, match_title,
+};
+use common::catalogue;
+
+#[test]
+fn describes_the_match() {
+    let mut catalogue = catalogue();
+    catalogue[0].title.as_mut().unwrap().user_preferred = None;
+
+    let result = match_title("Shingeki no Kyojin", &catalogue).unwrap();
+    assert_eq!(result.matched_field, Some(TitleField::Romaji));
+    assert_eq!(result.candidate_title.as_deref(), Some("Shingeki no Kyojin"));
+    assert_eq!(result.matched_via().as_deref(), Some("Romaji: Shingeki no Kyojin"));
+
+    let result = match_title("shingeki no kyojin", &catalogue).unwrap();
+    assert_eq!(result.matched_via().as_deref(), Some("Romaji: Shingeki no Kyojin"));
+
+    let result = match_title("鬼滅の刃", &catalogue).unwrap();
+    assert_eq!(result.matched_via().as_deref(), Some("Native: 鬼滅の刃"));
+}
+
+#[test]
+fn fuzzy_matches_report_the_closest_field() {
+    let catalogue = catalogue();
+    let search = ExpectAnime::from_string_title("Fullmetal Alchemist Brotherhod".into(), None, None);
+
+    let result = find_best_match(&search, &catalogue, |anime| anime).unwrap();
+    assert_eq!(result.matched_field, Some(TitleField::English));
+    assert_eq!(result.candidate_title.as_deref(), Some("Fullmetal Alchemist: Brotherhood"));
+}
+
+#[test]
+fn field_names() {
+    assert_eq!(TitleField::UserPreferred.to_string(), "User preferred");
+    assert_eq!(TitleField::Synonym.to_string(), "Synonym");
+    assert_eq!(serde_json::to_value(TitleField::UserPreferred).unwrap(), "user_preferred");
+
+    let result = match_title("Attack on Titan", &catalogue()).unwrap();
+    let json = serde_json::to_value(&result).unwrap();
+    assert_eq!(json["matched_field"], "english");
+    assert_eq!(json["candidate_title"], "Attack on Titan");
+}
+
+#[test]
+fn fields_can_be_audited_across_a_batch() {
+    let catalogue = catalogue();
+    let config = MatcherConfig::default();
+    let index = MatchIndex::new(config.normalizer().normalize_catalogue(&catalogue, |anime| anime));
+    let searches: Vec<ExpectAnime> = ["Attack on Titan", "Kimetsu no Yaiba", "ワンピース", "Steins Gate", "Zzzzqqq"]
+        .into_iter()
+        .map(|title| ExpectAnime::from_string_title(title.into(), None, None))
+        .collect();
+
+    let mut fields: HashMap<TitleField, usize> = HashMap::new();
+    for result in match_batch(&searches, &index, &config).into_iter().flatten() {
+        *fields.entry(result.matched_field.unwrap()).or_default() += 1;
+    }
+
+    assert_eq!(fields[&TitleField::English], 1);
+    assert_eq!(fields[&TitleField::UserPreferred], 2);
+    assert_eq!(fields[&TitleField::Native], 1);
+    assert_eq!(fields.values().sum::<usize>(), 4);
+}
+
+#[test]
+fn explain_reports_fields() {
+    let catalogue = catalogue();
+    let search = ExpectAnime::from_string_title("Shingeki no Kyojin".into(), None, None);
+    let explanation = explain(&search, &catalogue, |anime| anime, &MatcherConfig::default());
+
+    assert_eq!(explanation.search.fields, [TitleField::English]);
+
+    let tier = explanation.tiers.last().unwrap();
+    let pairs = &tier.candidates[0].pairs;
+    assert_eq!(
+        pairs.iter().map(|pair| pair.candidate_field).collect::<Vec<_>>(),
+        [TitleField::UserPreferred, TitleField::English, TitleField::Romaji, TitleField::Native]
+    );
+    assert!(pairs.iter().all(|pair| pair.search_field == TitleField::English));
+}
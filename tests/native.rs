@@ -0,0 +1,90 @@
+use am_algorithm::{
+    AnimeTitle, ExpectAnime, MatchMethod, Normalizer, find_best_match, fold_native,
+};
+
+/// Spellings of the same title, each of which must fold like the first
+const VARIANTS: &[&[&str]] = &[
+    // Middle dots
+    &[
+        "シュタインズ・ゲート",
+        "シュタインズゲート",
+        "シュタインズ･ゲート",
+    ],
+    // Hiragana and katakana, half-width katakana
+    &["けいおん", "ケイオン", "ｹｲｵﾝ"],
+    &["ゆるキャン", "ユルキャン", "ゆるきゃん"],
+    // Long vowel marks
+    &["ゲート", "ゲト"],
+    // Iteration marks
+    &["佐々木", "佐佐木"],
+    &["いすゞ", "いすず"],
+    &["こゝろ", "こころ"],
+    &["バナヽ", "バナナ"],
+    // Kanji variants
+    &["進撃の巨人", "進擊の巨人"],
+    &["鉄腕アトム", "鐵腕アトム"],
+    &["國", "国"],
+    &["澤", "沢"],
+    &["龍", "竜"],
+    &["劍", "剣"],
+    &["櫻", "桜"],
+];
+
+#[test]
+fn variants_fold_alike() {
+    for variants in VARIANTS {
+        let expected = fold_native(variants[0]);
+        for variant in *variants {
+            assert_eq!(fold_native(variant), expected, "{variant}");
+        }
+    }
+}
+
+#[test]
+fn folds_to_hiragana() {
+    assert_eq!(fold_native("シュタインズ・ゲート"), "しゅたいんずげと");
+    assert_eq!(fold_native("進撃の巨人"), "進撃の巨人");
+}
+
+#[test]
+fn leaves_latin_text_alone() {
+    for text in ["steins;gate", "re:zero", "k-on!", "a·b"] {
+        assert_eq!(fold_native(text), text);
+    }
+}
+
+#[test]
+fn sanitize_folds_native_titles() {
+    let normalizer = Normalizer::new();
+
+    assert_eq!(
+        normalizer.sanitize("シュタインズ・ゲート"),
+        normalizer.sanitize("シュタインズゲート")
+    );
+    assert_eq!(
+        normalizer.sanitize("けいおん！"),
+        normalizer.sanitize("ケイオン!")
+    );
+}
+
+#[test]
+fn native_variants_match_exactly() {
+    let catalogue = vec![ExpectAnime {
+        id: Some(serde_json::json!(1)),
+        title: Some(AnimeTitle {
+            native: Some("シュタインズ・ゲート".into()),
+            ..Default::default()
+        }),
+        year: Some(2011),
+        episodes: Some(24),
+        format: None,
+    }];
+
+    for title in ["シュタインズゲート", "しゅたいんず・げーと", "ｼｭﾀｲﾝｽﾞ･ｹﾞｰﾄ"]
+    {
+        let search = ExpectAnime::from_string_title(title.into(), None, None);
+        let m = find_best_match(&search, &catalogue, |anime| anime).unwrap();
+
+        assert_eq!(m.method, MatchMethod::ExactNormalized, "{title}");
+    }
+}
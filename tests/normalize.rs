@@ -0,0 +1,69 @@
+mod common;
+
+use am_algorithm::{
+    ExpectAnime, MatcherConfig, Normalizer, find_best_match_with, find_matches_with, sanitize_title,
+};
+use common::{catalogue, id_of};
+
+#[test]
+fn normalizer_matches_sanitize_title() {
+    let normalizer = Normalizer::new();
+
+    for title in [
+        "Attack on Titan Season 3 Part 2",
+        "Boku no Hero Academia 2nd Season",
+        "Naruto: Shippuuden (Uncut)",
+        "Yuu Yuu Hakusho",
+        "Pokémon",
+        "進撃の巨人",
+        "",
+    ] {
+        assert_eq!(Some(normalizer.sanitize(title)), sanitize_title(Some(title)), "{title}");
+    }
+
+    assert_eq!(normalizer.sanitize("Pokémon: The Series"), "pokemon the series");
+}
+
+#[test]
+fn normalizes_titles_in_order() {
+    let catalogue = catalogue();
+    let titles = Normalizer::new().normalize_titles(&catalogue[3].title);
+
+    assert_eq!(titles.raw, ["Steins;Gate", "Steins;Gate", "Steins;Gate", "シュタインズ・ゲート"]);
+    assert_eq!(titles.normalized, ["steins gate", "steins gate", "steins gate", "しゅたいんすけと"]);
+}
+
+#[test]
+fn normalized_catalogue_agrees_with_ad_hoc_search() {
+    let catalogue = catalogue();
+    let config = MatcherConfig::default();
+    let normalized = config.normalizer().normalize_catalogue(&catalogue, |anime| anime);
+
+    assert_eq!(normalized.len(), catalogue.len());
+
+    for (title, year, episodes) in [
+        ("Attack on Titan", Some(2013), Some(25)),
+        ("steins gate", Some(2011), None),
+        ("Kimetsu no Yaibba", None, None),
+        ("Boku no Hero Academia", None, None),
+        ("Zzzzqqq", None, None),
+    ] {
+        let search = ExpectAnime::from_string_title(title.into(), year, episodes);
+
+        let expected = find_best_match_with(&search, &catalogue, |anime| anime, &config);
+        let actual = normalized.find_best_match(&search, &config);
+        assert_eq!(
+            expected.map(|m| (id_of(&m.result), m.method, m.similarity, m.candidate_title)),
+            actual.map(|m| (id_of(&m.result), m.method, m.similarity, m.candidate_title)),
+            "{title}"
+        );
+
+        let expected = find_matches_with(&search, &catalogue, 3, |anime| anime, &config);
+        let actual = normalized.find_matches(&search, 3, &config);
+        assert_eq!(
+            expected.iter().map(|m| (id_of(&m.result), m.method)).collect::<Vec<_>>(),
+            actual.iter().map(|m| (id_of(&m.result), m.method)).collect::<Vec<_>>(),
+            "{title}"
+        );
+    }
+}
@@ -0,0 +1,127 @@
+mod common;
+
+use am_algorithm::{AnimeTitle, ExpectAnime, MatchIndex, MatchMethod, MatcherConfig, Normalizer, Pruning};
+use common::{catalogue, id_of};
+
+const WORDS: [&str; 16] = [
+    "kimetsu", "yaiba", "shingeki", "kyojin", "hero", "academia", "steins", "gate", "naruto", "bleach", "piece",
+    "alchemist", "sword", "online", "tokyo", "ghoul",
+];
+
+/// A larger catalogue of two and three word titles
+fn generated(size: usize) -> Vec<ExpectAnime> {
+    (0..size)
+        .map(|i| {
+            let title = format!(
+                "{} {} {}",
+                WORDS[i % WORDS.len()],
+                WORDS[(i / WORDS.len()) % WORDS.len()],
+                if i % 3 == 0 { i.to_string() } else { String::new() }
+            );
+
+            ExpectAnime {
+                id: Some(serde_json::json!(i)),
+                title: Some(AnimeTitle {
+                    english: Some(title.trim().to_string()),
+                    romaji: None,
+                    native: None,
+                    user_preferred: None,
+                    synonyms: Vec::new(),
+                }),
+                year: Some(2000 + (i % 20) as i32),
+                episodes: Some(12),
+                format: None,
+            }
+        })
+        .collect()
+}
+
+/// Searches with a character dropped from each candidate's title
+fn misspelled(catalogue: &[ExpectAnime]) -> Vec<ExpectAnime> {
+    catalogue
+        .iter()
+        .map(|anime| {
+            let title = anime.title.as_ref().unwrap().english.clone().unwrap();
+            let mut chars: Vec<char> = title.chars().collect();
+            chars.remove(chars.len() / 2);
+            ExpectAnime::from_string_title(chars.into_iter().collect(), None, None)
+        })
+        .collect()
+}
+
+#[test]
+fn finds_misspelled_titles() {
+    let catalogue = catalogue();
+    let config = MatcherConfig::default();
+    let index = MatchIndex::new(Normalizer::new().normalize_catalogue(&catalogue, |anime| anime))
+        .with_pruning(Pruning { shortlist: 2, n: 3 });
+
+    assert_eq!(index.pruning(), Some(Pruning { shortlist: 2, n: 3 }));
+
+    let search = ExpectAnime::from_string_title("Kimetsu no Yaibba".into(), None, None);
+    let result = index.find_best_match(&search, &config).unwrap();
+    assert_eq!(id_of(&result.result), 2);
+    assert_eq!(result.method, MatchMethod::Loose);
+
+    assert_eq!(index.recall(&misspelled(&catalogue), &config), 1.0);
+}
+
+#[test]
+fn large_shortlist_prunes_nothing() {
+    let catalogue = generated(120);
+    let searches = misspelled(&catalogue);
+    let config = MatcherConfig::default();
+    let normalized = Normalizer::new().normalize_catalogue(&catalogue, |anime| anime);
+    let exhaustive = MatchIndex::new(normalized.clone());
+    let pruned = MatchIndex::new(normalized).with_pruning(Pruning {
+        shortlist: catalogue.len(),
+        n: 3,
+    });
+
+    assert_eq!(pruned.recall(&searches, &config), 1.0);
+
+    for search in &searches {
+        assert_eq!(
+            exhaustive.find_matches(search, 5, &config).iter().map(|m| id_of(&m.result)).collect::<Vec<_>>(),
+            pruned.find_matches(search, 5, &config).iter().map(|m| id_of(&m.result)).collect::<Vec<_>>(),
+        );
+    }
+}
+
+#[test]
+fn pruned_matches_are_real_matches() {
+    let catalogue = generated(120);
+    let config = MatcherConfig::default();
+    let normalized = Normalizer::new().normalize_catalogue(&catalogue, |anime| anime);
+    let exhaustive = MatchIndex::new(normalized.clone());
+    let pruned = MatchIndex::new(normalized).with_pruning(Pruning { shortlist: 5, n: 3 });
+
+    for search in misspelled(&catalogue) {
+        let all = exhaustive.find_matches(&search, catalogue.len(), &config);
+
+        for m in pruned.find_matches(&search, catalogue.len(), &config) {
+            assert!(all.iter().any(|other| other.result.id == m.result.id
+                && other.method == m.method
+                && other.similarity == m.similarity));
+        }
+    }
+}
+
+#[test]
+fn recall_against_brute_force() {
+    let catalogue = generated(200);
+    let searches = misspelled(&catalogue);
+    let config = MatcherConfig::default();
+    let normalized = Normalizer::new().normalize_catalogue(&catalogue, |anime| anime);
+
+    let recall = |shortlist| {
+        MatchIndex::new(normalized.clone())
+            .with_pruning(Pruning { shortlist, n: 3 })
+            .recall(&searches, &config)
+    };
+
+    assert_eq!(MatchIndex::new(normalized.clone()).recall(&searches, &config), 1.0);
+    assert!(recall(1) <= recall(10));
+    assert!(recall(10) <= recall(20));
+    assert!(recall(20) >= 0.95, "{}", recall(20));
+}
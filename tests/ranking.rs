@@ -0,0 +1,64 @@
+mod common;
+
+use am_algorithm::{ExpectAnime, MatchMethod, find_best_match, find_matches};
+use common::{anime, catalogue, id_of};
+
+#[test]
+fn first_entry_agrees_with_find_best_match() {
+    let catalogue = catalogue();
+
+    for (title, year, episodes) in [
+        ("Attack on Titan", Some(2013), Some(25)),
+        ("steins gate", Some(2011), None),
+        ("Kimetsu no Yaibba", None, None),
+        ("Boku no Hero Academia", None, None),
+        ("Bleach", Some(2022), None),
+    ] {
+        let search = ExpectAnime::from_string_title(title.into(), year, episodes);
+        let best = find_best_match(&search, &catalogue, |anime| anime).unwrap();
+        let ranked = find_matches(&search, &catalogue, 3, |anime| anime);
+
+        assert_eq!(id_of(&ranked[0].result), id_of(&best.result), "{title}");
+        assert_eq!(ranked[0].method, best.method, "{title}");
+        assert_eq!(ranked[0].similarity, best.similarity, "{title}");
+    }
+}
+
+#[test]
+fn exact_tiers_rank_above_fuzzy_ones() {
+    let catalogue = vec![
+        anime(1, "Naruto Shippuden", "Naruto: Shippuuden", "ナルト 疾風伝", 2007, 500),
+        anime(2, "Naruto", "Naruto", "ナルト", 2002, 220),
+        anime(3, "Boruto", "Boruto: Naruto Next Generations", "ボルト", 2017, 293),
+    ];
+    let search = ExpectAnime::from_string_title("Naruto".into(), None, None);
+
+    let ranked = find_matches(&search, &catalogue, 10, |anime| anime);
+
+    assert_eq!(id_of(&ranked[0].result), 2);
+    assert_eq!(ranked[0].method, MatchMethod::Exact);
+    assert!(ranked.len() >= 2);
+    assert!(ranked[1..].iter().all(|m| m.similarity < 1.0));
+}
+
+#[test]
+fn reports_the_candidate_title_that_matched() {
+    let catalogue = catalogue();
+    let search = ExpectAnime::from_string_title("Shingeki no Kyojin".into(), None, None);
+
+    let ranked = find_matches(&search, &catalogue, 1, |anime| anime);
+
+    assert_eq!(ranked[0].candidate_title.as_deref(), Some("Shingeki no Kyojin"));
+}
+
+#[test]
+fn limits_to_n_and_skips_non_matches() {
+    let catalogue = catalogue();
+    let search = ExpectAnime::from_string_title("Zzzzqqq".into(), None, None);
+
+    assert!(find_matches(&search, &catalogue, 5, |anime| anime).is_empty());
+
+    let search = ExpectAnime::from_string_title("Bleach".into(), None, None);
+    assert_eq!(find_matches(&search, &catalogue, 0, |anime| anime).len(), 0);
+    assert!(find_matches(&search, &catalogue, 2, |anime| anime).len() <= 2);
+}
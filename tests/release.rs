@@ -0,0 +1,137 @@
+mod common;
+
+use am_algorithm::{ExpectAnime, MatchMethod, ParsedRelease, SeasonInfo, find_best_match};
+use common::{catalogue, id_of};
+
+fn parse(filename: &str) -> ParsedRelease {
+    ParsedRelease::parse(filename).unwrap_or_else(|| panic!("no title in {filename}"))
+}
+
+#[test]
+fn parses_fansub_filenames() {
+    let release = parse("[SubsPlease] Kimetsu no Yaiba - 05 (1080p) [ABCD1234].mkv");
+
+    assert_eq!(
+        release,
+        ParsedRelease {
+            group: Some("SubsPlease".into()),
+            title: "Kimetsu no Yaiba".into(),
+            episode: Some(5),
+            season: None,
+            version: None,
+            year: None,
+            resolution: Some("1080p".into()),
+            source: None,
+            codec: None,
+            checksum: Some("ABCD1234".into()),
+        }
+    );
+}
+
+#[test]
+fn parses_titles_and_episodes() {
+    for (filename, title, episode) in [
+        (
+            "[Erai-raws] Shingeki no Kyojin - The Final Season - 12 [720p].mkv",
+            "Shingeki no Kyojin - The Final Season",
+            Some(12),
+        ),
+        (
+            "[Judas] Re:Zero kara Hajimeru Isekai Seikatsu - 03v2 [1080p][HEVC x265 10bit].mkv",
+            "Re:Zero kara Hajimeru Isekai Seikatsu",
+            Some(3),
+        ),
+        ("Mob Psycho 100 - 07 [BD 1080p].mkv", "Mob Psycho 100", Some(7)),
+        ("Steins;Gate Episode 4 [DVD].avi", "Steins;Gate", Some(4)),
+        ("One_Piece_Ep_1071_[1080p].mp4", "One Piece", Some(1071)),
+        ("Spy.x.Family.E07.1080p.WEB-DL.x264.mkv", "Spy x Family", Some(7)),
+        ("[Group] Cowboy Bebop The Movie (2001) [BD 1080p].mkv", "Cowboy Bebop The Movie", None),
+        ("[Group] Fullmetal Alchemist Brotherhood [Batch]", "Fullmetal Alchemist Brotherhood", None),
+    ] {
+        let release = parse(filename);
+        assert_eq!(release.title, title, "{filename}");
+        assert_eq!(release.episode, episode, "{filename}");
+    }
+}
+
+#[test]
+fn parses_seasons() {
+    for (filename, season) in [
+        ("Jujutsu.Kaisen.S02E05.1080p.WEB-DL.x264.mkv", Some(2)),
+        ("[SubsPlease] Spy x Family Season 2 - 01 (1080p) [F00DCAFE].mkv", Some(2)),
+        ("[SubsPlease] Oshi no Ko 2nd Season - 03 (720p).mkv", Some(2)),
+        ("[SubsPlease] Kimetsu no Yaiba - 05 (1080p).mkv", None),
+    ] {
+        assert_eq!(parse(filename).season, season, "{filename}");
+    }
+}
+
+#[test]
+fn parses_tags() {
+    let release = parse("[Judas] Re:Zero kara Hajimeru Isekai Seikatsu - 03v2 [1080p][HEVC x265 10bit].mkv");
+    assert_eq!(release.group.as_deref(), Some("Judas"));
+    assert_eq!(release.version, Some(2));
+    assert_eq!(release.codec.as_deref(), Some("HEVC"));
+
+    let release = parse("Jujutsu.Kaisen.S02E05.1080p.WEB-DL.x264.mkv");
+    assert_eq!(release.group, None);
+    assert_eq!(release.resolution.as_deref(), Some("1080p"));
+    assert_eq!(release.source.as_deref(), Some("WEB-DL"));
+    assert_eq!(release.codec.as_deref(), Some("x264"));
+
+    let release = parse("[Group] Cowboy Bebop The Movie (2001) [BDRip 1920x1080 AVC][deadbeef].mkv");
+    assert_eq!(release.year, Some(2001));
+    assert_eq!(release.resolution.as_deref(), Some("1920x1080"));
+    assert_eq!(release.source.as_deref(), Some("BDRip"));
+    assert_eq!(release.codec.as_deref(), Some("AVC"));
+    assert_eq!(release.checksum.as_deref(), Some("DEADBEEF"));
+}
+
+#[test]
+fn keeps_tag_words_inside_the_title() {
+    for (filename, title, source) in [
+        ("[SubsPlease] Web Ghost PiPoPa - 01 (1080p) [ABCD1234].mkv", "Web Ghost PiPoPa", None),
+        ("[Group] DVD Collection Show - 03 [720p].mkv", "DVD Collection Show", None),
+        ("Bd.Show.S01E05.1080p.WEB-DL.x264.mkv", "Bd Show", Some("WEB-DL")),
+        ("[Group] Ghost Web Show 1080p WEB - 02.mkv", "Ghost Web Show", Some("WEB")),
+        ("Show.1080p.WEB-DL.x264-GROUP.mkv", "Show", Some("WEB-DL")),
+    ] {
+        let release = parse(filename);
+        assert_eq!(release.title, title, "{filename}");
+        assert_eq!(release.source.as_deref(), source, "{filename}");
+    }
+}
+
+#[test]
+fn rejects_filenames_without_a_title() {
+    assert_eq!(ParsedRelease::parse("[SubsPlease] (1080p) [ABCD1234].mkv"), None);
+    assert_eq!(ParsedRelease::parse(""), None);
+}
+
+#[test]
+fn converts_to_a_search() {
+    let search = ExpectAnime::from(parse("[Group] Cowboy Bebop The Movie (2001) [BD 1080p].mkv"));
+    let title = search.title.unwrap();
+    assert_eq!(title.user_preferred.as_deref(), Some("Cowboy Bebop The Movie"));
+    assert_eq!(search.year, Some(2001));
+    assert_eq!(search.episodes, None);
+
+    // A season only named by the tags is carried over to the title
+    let search = ExpectAnime::from(parse("Jujutsu.Kaisen.S02E05.1080p.WEB-DL.x264.mkv"));
+    let title = search.title.unwrap().user_preferred.unwrap();
+    assert_eq!(title, "Jujutsu Kaisen Season 2");
+    assert_eq!(SeasonInfo::parse(&title).season, Some(2));
+
+    let search = ExpectAnime::from(parse("[SubsPlease] Oshi no Ko 2nd Season - 03 (720p).mkv"));
+    assert_eq!(search.title.unwrap().user_preferred.as_deref(), Some("Oshi no Ko 2nd Season"));
+}
+
+#[test]
+fn parsed_release_finds_its_anime() {
+    let catalogue = catalogue();
+    let search = ExpectAnime::from(parse("[SubsPlease] Kimetsu no Yaiba - 05 (1080p) [ABCD1234].mkv"));
+
+    let m = find_best_match(&search, &catalogue, |anime| anime).unwrap();
+    assert_eq!(id_of(&m.result), 2);
+    assert_eq!(m.method, MatchMethod::Exact);
+}
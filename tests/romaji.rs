@@ -0,0 +1,122 @@
+mod common;
+
+use am_algorithm::{ExpectAnime, MatchMethod, Normalizer, canonicalize_romaji, find_best_match};
+use common::{anime, catalogue, id_of};
+
+/// Spellings of the same reading, each of which must canonicalize to the first
+const VARIANTS: &[&[&str]] = &[
+    // Long vowels
+    &["kyojin", "kyoujin", "kyoojin", "kyohjin"],
+    &["shippuden", "shippuuden"],
+    &["yu yu hakusho", "yuu yuu hakusho"],
+    &["osama", "ousama", "oosama", "ohsama"],
+    &["onisan", "oniisan"],
+    &["neko", "nekoo"],
+    &["ya", "yaa"],
+    &["toru", "tohru", "touru"],
+    // Kunrei and Nihon-shiki
+    &["shinobu", "sinobu"],
+    &["chihiro", "tihiro"],
+    &["tsuki", "tuki"],
+    &["fuji", "huzi", "fuzi", "fudi"],
+    &["jujutsu", "zyuzyutu", "jyujutsu", "juujutsu"],
+    &["shota", "syouta", "shouta", "shohta"],
+    &["chainsa", "tyainsa"],
+    &["tsuzuki", "tuduki"],
+    // Syllabic n and doubled consonants
+    &["shinbun", "shimbun"],
+    &["sanpo", "sampo"],
+    &["matcha", "maccha"],
+    &["kitto", "kitto"],
+    &["konnichiwa", "konnitiwa"],
+    // Particles
+    &["kore wa", "kore ha"],
+    &["shingeki o", "shingeki wo"],
+    &["tokyo e", "toukyou he"],
+];
+
+/// Words that are not romaji and must be left alone
+const UNCHANGED: &[&str] = &[
+    "cowboy bebop",
+    "sword art online",
+    "fullmetal alchemist brotherhood",
+    "steins gate",
+    "one piece",
+    "86 eighty six",
+    "re zero",
+    "進撃の巨人",
+    "hunter x hunter",
+    "ii",
+    "iii",
+    "vii",
+    "viii",
+    "sword art online iii",
+    "final fantasy viii",
+];
+
+#[test]
+fn variants_share_one_spelling() {
+    for variants in VARIANTS {
+        for variant in *variants {
+            assert_eq!(canonicalize_romaji(variant), variants[0], "{variant}");
+        }
+    }
+}
+
+#[test]
+fn leaves_other_words_alone() {
+    for text in UNCHANGED {
+        assert_eq!(canonicalize_romaji(text), *text);
+    }
+}
+
+#[test]
+fn sanitize_folds_macrons_and_romanizations() {
+    let normalizer = Normalizer::new();
+
+    for (title, expected) in [
+        ("Shingeki no Kyōjin", "shingeki no kyojin"),
+        ("Shingeki no Kyoujin", "shingeki no kyojin"),
+        ("Naruto: Shippūden", "naruto shippuden"),
+        ("Naruto Shippuuden", "naruto shippuden"),
+        ("Jujutsu Kaisen", "jujutsu kaisen"),
+        ("Zyuzyutu Kaisen", "jujutsu kaisen"),
+        ("Yuu☆Yuu☆Hakusho", "yu yu hakusho"),
+    ] {
+        assert_eq!(normalizer.sanitize(title), expected, "{title}");
+    }
+}
+
+#[test]
+fn romanizations_match_exactly() {
+    let catalogue = catalogue();
+
+    for title in [
+        "Shingeki no Kyoujin",
+        "Shingeki no Kyohjin",
+        "Singeki no Kyoujin",
+    ] {
+        let search = ExpectAnime::from_string_title(title.into(), None, None);
+        let m = find_best_match(&search, &catalogue, |anime| anime).unwrap();
+
+        assert_eq!(id_of(&m.result), 1, "{title}");
+        assert_eq!(m.method, MatchMethod::ExactNormalized, "{title}");
+    }
+}
+
+#[test]
+fn roman_numerals_keep_their_length() {
+    let normalizer = Normalizer::new();
+    assert_ne!(normalizer.sanitize("Sword Art Online II"), normalizer.sanitize("Sword Art Online III"));
+    assert_ne!(normalizer.sanitize("Final Fantasy VII"), normalizer.sanitize("Final Fantasy VIII"));
+
+    let catalogue = vec![
+        anime(1, "Berserk: Ougon Jidai-hen II", "Berserk: Ougon Jidai-hen II", "ベルセルク 黄金時代篇II", 2012, 1),
+        anime(2, "Berserk: Ougon Jidai-hen I", "Berserk: Ougon Jidai-hen I", "ベルセルク 黄金時代篇I", 2012, 1),
+    ];
+    let search = ExpectAnime::from_string_title("Berserk Ougon Jidai-hen I".into(), None, None);
+    let m = find_best_match(&search, &catalogue, |anime| anime).unwrap();
+
+    assert_eq!(id_of(&m.result), 2);
+    assert_eq!(m.method, MatchMethod::ExactNormalized);
+}
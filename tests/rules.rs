@@ -0,0 +1,166 @@
+mod common;
+
+use am_algorithm::{
+    ExpectAnime, MatchMethod, MatcherConfig, NormalizationRules, Normalizer, RuleError, RulesError,
+    find_best_match_with,
+};
+use common::{catalogue, id_of};
+
+const RULES: &str = r#"
+remove = ["the animation", "tv anime", "gekijouban"]
+
+[[replace]]
+pattern = '\bmovie\s*(\d+)'
+with = "$1"
+
+[[replace]]
+pattern = "&"
+with = " and "
+
+[aliases]
+aot = "attack on titan"
+"boku no" = "my"
+"#;
+
+fn normalizer() -> Normalizer {
+    Normalizer::new().with_rules(NormalizationRules::from_toml(RULES).unwrap())
+}
+
+fn invalid(text: &str) -> Vec<RuleError> {
+    match NormalizationRules::from_toml(text) {
+        Err(RulesError::Invalid(errors)) => errors,
+        other => panic!("expected invalid rules, got {other:?}"),
+    }
+}
+
+#[test]
+fn rules_rewrite_titles() {
+    let normalizer = normalizer();
+
+    for (title, expected) in [
+        ("Gekijouban Kimetsu no Yaiba", "kimetsu no yaiba"),
+        ("Yuru Camp The Animation", "yuru camp"),
+        ("TV Anime Yuru Camp", "yuru camp"),
+        ("Conan Movie 25", "conan 25"),
+        ("Conan Movie25", "conan 25"),
+        ("Bread & Butter", "bread and butter"),
+        ("Boku no Hero Academia", "my hero academia"),
+        ("Boku  no Hero Academia", "my hero academia"),
+        ("Bokuno Hero", "bokuno hero"),
+        ("Aoto", "aoto"),
+    ] {
+        assert_eq!(normalizer.sanitize(title), expected, "{title}");
+    }
+
+    assert_eq!(normalizer.sanitize("AoT"), normalizer.sanitize("Attack on Titan"));
+}
+
+#[test]
+fn replacements_are_lowercased() {
+    let rules = NormalizationRules::from_toml(
+        "[[replace]]\npattern = '\\bmovie\\b'\nwith = \"Film\"\n\n\
+         [[replace]]\npattern = '(?<Title>\\w+) gekijouban'\nwith = \"Gekijouban ${Title}\"\n",
+    )
+    .unwrap();
+    let normalizer = Normalizer::new().with_rules(rules);
+
+    assert_eq!(normalizer.sanitize("Violet Evergarden Movie"), "violet evergarden film");
+    assert_eq!(normalizer.sanitize("Conan Gekijouban"), "gekijoban conan");
+    assert_eq!(normalizer.sanitize("Violet Evergarden Movie"), normalizer.sanitize("Violet Evergarden Film"));
+}
+
+#[test]
+fn aliases_are_not_rewritten_again() {
+    let rules = NormalizationRules::from_toml("[aliases]\nmy = \"boku no\"\n\"boku no\" = \"my\"\n").unwrap();
+    let normalizer = Normalizer::new().with_rules(rules);
+
+    assert_eq!(normalizer.sanitize("My Boku no"), "boku no my");
+}
+
+#[test]
+fn empty_rules_change_nothing() {
+    let rules = NormalizationRules::from_toml("").unwrap();
+    assert!(rules.is_empty());
+    assert!(Normalizer::new().rules().is_empty());
+
+    let normalizer = Normalizer::new().with_rules(rules);
+    for title in ["Attack on Titan", "Gekijouban Naruto", "進撃の巨人"] {
+        assert_eq!(normalizer.sanitize(title), Normalizer::new().sanitize(title));
+    }
+}
+
+#[test]
+fn rules_apply_when_matching() {
+    let catalogue = catalogue();
+    let config = MatcherConfig::default().with_normalizer(normalizer());
+
+    let search = ExpectAnime::from_string_title("AOT".into(), None, None);
+    let m = find_best_match_with(&search, &catalogue, |anime| anime, &config).unwrap();
+
+    assert_eq!(id_of(&m.result), 1);
+    assert_eq!(m.method, MatchMethod::ExactNormalized);
+}
+
+#[test]
+fn reports_every_invalid_rule_by_line() {
+    let errors = invalid(
+        r#"remove = [
+    "gekijouban",
+    "",
+]
+
+[[replace]]
+pattern = "(unclosed"
+
+[[replace]]
+pattern = 'fine'
+
+[aliases]
+Gekijouban = "movie"
+"#,
+    );
+
+    let lines: Vec<usize> = errors.iter().map(|error| error.line).collect();
+    assert_eq!(lines, [3, 7, 13]);
+    assert_eq!(errors[0].message, "empty word");
+    assert!(errors[1].message.starts_with("invalid pattern `(unclosed`"));
+    assert_eq!(errors[2].message, "`Gekijouban` is already rewritten on line 2");
+}
+
+#[test]
+fn reports_syntax_errors_by_line() {
+    let errors = invalid("remove = [\"a\"]\n\n[aliases\n");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].line, 3);
+
+    let errors = invalid("remove = [\"a\"]\nrename = [\"b\"]\n");
+    assert_eq!(errors[0].line, 2);
+    assert!(errors[0].message.contains("rename"));
+
+    let errors = invalid("[[replace]]\nwith = \"b\"\n");
+    assert!(errors[0].message.contains("pattern"));
+}
+
+#[test]
+fn displays_errors_one_per_line() {
+    let error = NormalizationRules::from_toml("remove = [\"\", \"a\", \"a\"]\n").unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "invalid rules:\nline 1: empty word\nline 1: `a` is already rewritten on line 1"
+    );
+}
+
+#[test]
+fn loads_rules_from_a_file() {
+    let path = std::env::temp_dir().join(format!("am-algorithm-rules-{}.toml", std::process::id()));
+    std::fs::write(&path, RULES).unwrap();
+
+    let rules = NormalizationRules::from_file(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    let normalizer = Normalizer::new().with_rules(rules.unwrap());
+    assert_eq!(normalizer.sanitize("AOT"), normalizer.sanitize("Attack on Titan"));
+
+    assert!(matches!(NormalizationRules::from_file(&path), Err(RulesError::Io(_))));
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use am_algorithm::{
+    ExpectAnime, MatchMethod, MatcherConfig, SeasonInfo, SeasonMismatch, explain, find_best_match_with, match_title,
+};
+use common::{anime, catalogue, id_of};
+
+fn info(season: Option<u32>, part: Option<u32>, cour: Option<u32>) -> SeasonInfo {
+    SeasonInfo { season, part, cour }
+}
+
+#[test]
+fn parses_season_markers() {
+    for (title, expected) in [
+        ("Boku no Hero Academia 2nd Season", info(Some(2), None, None)),
+        ("My Hero Academia Season 2", info(Some(2), None, None)),
+        ("My Hero Academia season2", info(Some(2), None, None)),
+        ("Kaguya-sama wa Kokurasetai: Third Season", info(Some(3), None, None)),
+        ("Attack on Titan S3", info(Some(3), None, None)),
+        ("Naruto S01E05", info(Some(1), None, None)),
+        ("Overlord II", info(Some(2), None, None)),
+        ("Dungeon ni Deai wo Motomeru no wa Machigatteiru Darou ka IV", info(Some(4), None, None)),
+        ("Dungeon ni Deai wo Motomeru no wa Machigatteiru Darou ka Season V", info(Some(5), None, None)),
+        ("僕のヒーローアカデミア 第2期", info(Some(2), None, None)),
+        ("僕のヒーローアカデミア 第２期", info(Some(2), None, None)),
+        ("進撃の巨人 第三期", info(Some(3), None, None)),
+        ("ダンまち 第十二期", info(Some(12), None, None)),
+        ("Shingeki no Kyojin Season 3 Part 2", info(Some(3), Some(2), None)),
+        ("Re:Zero kara Hajimeru Isekai Seikatsu Part II", info(None, Some(2), None)),
+        ("JoJo no Kimyou na Bouken 2nd Part", info(None, Some(2), None)),
+        ("Jujutsu Kaisen 2nd Season Cour 2", info(Some(2), None, Some(2))),
+        ("呪術廻戦 第2クール", info(None, None, Some(2))),
+    ] {
+        assert_eq!(SeasonInfo::parse(title), expected, "{title}");
+    }
+}
+
+#[test]
+fn leaves_other_numbers_alone() {
+    for title in [
+        "Fullmetal Alchemist",
+        "Mob Psycho 100",
+        "Steins;Gate 0",
+        "Spy x Family",
+        "Kimetsu no Yaiba",
+        "Vinland Saga",
+        "ワンピース",
+        "Hunter X Hunter",
+        "Mobile Suit Gundam X",
+        "X-Men",
+        "Gundam Build Fighters V",
+    ] {
+        assert!(SeasonInfo::parse(title).is_empty(), "{title}");
+    }
+}
+
+#[test]
+fn conflicts_need_numbers_on_both_sides() {
+    let second = info(Some(2), None, None);
+
+    assert!(second.conflicts_with(&info(Some(3), None, None)));
+    assert!(!second.conflicts_with(&info(Some(2), Some(1), None)));
+    assert!(info(Some(3), Some(1), None).conflicts_with(&info(Some(3), Some(2), None)));
+    assert!(!second.conflicts_with(&SeasonInfo::default()));
+    assert!(!second.conflicts_with(&second));
+}
+
+#[test]
+fn rejects_other_seasons() {
+    let mut catalogue = catalogue();
+    catalogue.push(anime(
+        9,
+        "My Hero Academia Season 3",
+        "Boku no Hero Academia 3rd Season",
+        "僕のヒーローアカデミア 第3期",
+        2018,
+        25,
+    ));
+
+    let result = match_title("Boku no Hero Academia Season 3", &catalogue).unwrap();
+    assert_eq!(id_of(&result.result), 9);
+
+    let result = match_title("My Hero Academia 2nd Season", &catalogue).unwrap();
+    assert_eq!(id_of(&result.result), 3);
+
+    catalogue.pop();
+    let result = match_title("My Hero Academia Season 3", &catalogue);
+    assert!(result.is_none_or(|m| id_of(&m.result) != 3));
+}
+
+#[test]
+fn mismatch_can_be_penalized_or_ignored() {
+    let catalogue = catalogue();
+    let search = ExpectAnime::from_string_title("My Hero Academia Season 3".into(), None, None);
+
+    let ignore = MatcherConfig::default().with_season_mismatch(SeasonMismatch::Ignore);
+    let ignored = find_best_match_with(&search, &catalogue, |anime| anime, &ignore).unwrap();
+    assert_eq!(id_of(&ignored.result), 3);
+    assert_eq!(ignored.method, MatchMethod::Loose);
+
+    let penalty = MatcherConfig::default().with_season_mismatch(SeasonMismatch::Penalty(0.1));
+    let penalized = find_best_match_with(&search, &catalogue, |anime| anime, &penalty).unwrap();
+    assert_eq!(id_of(&penalized.result), 3);
+    assert!((ignored.similarity - penalized.similarity - 0.1).abs() < 1e-9);
+}
+
+#[test]
+fn missing_season_is_not_a_mismatch() {
+    let result = match_title("My Hero Academia", &catalogue()).unwrap();
+    assert_eq!(id_of(&result.result), 3);
+}
+
+#[test]
+fn explain_flags_conflicting_pairs() {
+    let catalogue = catalogue();
+    let search = ExpectAnime::from_string_title("My Hero Academia Season 3".into(), None, None);
+    let explanation = explain(&search, &catalogue, |anime| anime, &MatcherConfig::default());
+
+    let loose = explanation.tiers.iter().find(|tier| tier.method == MatchMethod::Loose).unwrap();
+    let pairs = &loose.candidates[2].pairs;
+    assert!(pairs.iter().all(|pair| pair.season_conflict));
+    assert!(loose.candidates[2].similarity.is_none());
+    assert!(!loose.candidates[0].pairs.iter().any(|pair| pair.season_conflict));
+}
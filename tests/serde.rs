@@ -0,0 +1,101 @@
+mod common;
+
+use std::collections::HashSet;
+
+use am_algorithm::{
+    Confidence, ExpectAnime, MatchMethod, MatchResult, MatcherConfig, YearMatch, find_best_match,
+    find_best_match_with, match_title,
+};
+use common::{catalogue, id_of};
+
+#[test]
+fn match_result_round_trips_through_json() {
+    let catalogue = catalogue();
+    let search = ExpectAnime::from_string_title("Attack on Titan".into(), Some(2013), Some(25));
+    let m = find_best_match(&search, &catalogue, |anime| anime).unwrap();
+
+    let json = serde_json::to_value(&m).unwrap();
+    assert_eq!(json["method"], "exact_year_episode_raw");
+    assert_eq!(json["year_match"], "exact");
+    assert_eq!(json["result"]["id"], 1);
+
+    let back: MatchResult<ExpectAnime> = serde_json::from_value(json).unwrap();
+    assert_eq!(back.method, m.method);
+    assert_eq!(back.similarity, m.similarity);
+    assert_eq!(back.title, m.title);
+    assert_eq!(back.year_match, m.year_match);
+    assert_eq!(id_of(&back.result), 1);
+}
+
+#[test]
+fn method_names_are_stable_snake_case() {
+    let names: Vec<String> = MatchMethod::ALL
+        .iter()
+        .map(|m| serde_json::to_value(m).unwrap().as_str().unwrap().to_string())
+        .collect();
+
+    assert_eq!(
+        names,
+        [
+            "exact_year_episode_raw",
+            "exact_year_episode_normalized",
+            "exact_year_raw",
+            "exact_year_normalized",
+            "exact",
+            "exact_normalized",
+            "loose_year",
+            "loose",
+            "last_resort",
+            "null_method",
+        ]
+    );
+    assert_eq!(serde_json::from_str::<MatchMethod>("\"composite\"").unwrap(), MatchMethod::Composite);
+    assert_eq!(
+        serde_json::to_value(YearMatch::Within(1)).unwrap(),
+        serde_json::json!({ "within": 1 })
+    );
+}
+
+#[test]
+fn methods_order_by_tier_strength() {
+    assert!(MatchMethod::ExactYearEpisodeRaw > MatchMethod::Exact);
+    assert!(MatchMethod::ExactNormalized > MatchMethod::LooseYear);
+    assert!(MatchMethod::LastResort > MatchMethod::NullMethod);
+    assert!(MatchMethod::NullMethod > MatchMethod::Transliterated);
+    assert!(MatchMethod::Transliterated > MatchMethod::Composite);
+
+    let mut sorted = MatchMethod::ALL.to_vec();
+    sorted.sort_by(|a, b| b.cmp(a));
+    assert_eq!(sorted, MatchMethod::ALL);
+
+    let mut every = vec![MatchMethod::Composite, MatchMethod::Transliterated];
+    every.extend(MatchMethod::ALL);
+    every.sort_by(|a, b| b.cmp(a));
+    every.dedup_by(|a, b| a.cmp(&b).is_eq());
+    assert_eq!(every.len(), MatchMethod::ALL.len() + 2);
+    assert_eq!(every[..MatchMethod::ALL.len()], MatchMethod::ALL);
+    assert_eq!(every[MatchMethod::ALL.len()..], [MatchMethod::Transliterated, MatchMethod::Composite]);
+
+    let unique: HashSet<MatchMethod> = every.iter().copied().collect();
+    assert_eq!(unique.len(), every.len());
+}
+
+#[test]
+fn confidence_follows_the_tier() {
+    let catalogue = catalogue();
+
+    assert_eq!(match_title("Attack on Titan", &catalogue).unwrap().confidence(), Confidence::High);
+    assert_eq!(match_title("Kimetsu no Yaibba", &catalogue).unwrap().confidence(), Confidence::Medium);
+
+    let search = ExpectAnime::from_string_title("Attack on Titan".into(), Some(2014), None);
+    let config = MatcherConfig::new().with_year_tolerance(1);
+    let m = find_best_match_with(&search, &catalogue, |anime| anime, &config).unwrap();
+    assert_eq!(m.confidence(), Confidence::Medium);
+
+    let config = MatcherConfig::new().with_tiers([MatchMethod::NullMethod]);
+    let search = ExpectAnime::from_string_title("Attack on Titan".into(), None, None);
+    let m = find_best_match_with(&search, &catalogue, |anime| anime, &config).unwrap();
+    assert_eq!(m.confidence(), Confidence::Low);
+
+    assert!(Confidence::High > Confidence::Medium && Confidence::Medium > Confidence::Low);
+}
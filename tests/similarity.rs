@@ -0,0 +1,167 @@
+mod common;
+
+use am_algorithm::{
+    DamerauLevenshtein, ExpectAnime, JaroWinkler, Levenshtein, MatchMethod, MatcherConfig,
+    NgramCosine, NgramJaccard, Similarity, TokenSetRatio, TokenSortRatio, find_best_match_with,
+    jaro_winkler_distance, jaro_winkler_graphemes,
+};
+use common::{catalogue, id_of};
+use proptest::prelude::*;
+
+fn metrics() -> Vec<Box<dyn Similarity>> {
+    vec![
+        Box::new(JaroWinkler::default()),
+        Box::new(Levenshtein),
+        Box::new(DamerauLevenshtein),
+        Box::new(TokenSortRatio),
+        Box::new(TokenSetRatio),
+        Box::new(NgramJaccard::default()),
+        Box::new(NgramCosine::default()),
+    ]
+}
+
+/// Titles mixing Latin, accented Latin, kana, kanji and hangul
+fn mixed_script() -> impl Strategy<Value = String> {
+    "[a-zé ーの進撃巨人ナルト疾風伝한국]{0,16}"
+}
+
+#[test]
+fn native_titles_score_by_character() {
+    // One differing character out of five in both cases
+    let native = jaro_winkler_distance("進撃の巨人", "進撃の巨大", None);
+    let ascii = jaro_winkler_distance("abcde", "abcdx", None);
+
+    assert!((native - ascii).abs() < 1e-12);
+    assert!((native - 0.92).abs() < 1e-12);
+}
+
+#[test]
+fn native_and_ascii_transpositions_agree() {
+    let native = jaro_winkler_distance("ナルト", "ナトル", None);
+    let ascii = jaro_winkler_distance("abc", "acb", None);
+
+    assert!((native - ascii).abs() < 1e-12);
+}
+
+#[test]
+fn graphemes_treat_combining_marks_as_one_unit() {
+    // "e" followed by a combining acute accent, against the precomposed "é"
+    let decomposed = "cafe\u{301}s";
+    let by_grapheme = jaro_winkler_graphemes(decomposed, "cafxs", None);
+    let by_char = jaro_winkler_distance("caf\u{e9}s", "cafxs", None);
+
+    assert!((by_grapheme - by_char).abs() < 1e-12);
+}
+
+#[test]
+fn edit_distances() {
+    assert!((Levenshtein.similarity("kitten", "sitting") - (1.0 - 3.0 / 7.0)).abs() < 1e-12);
+    assert!((Levenshtein.similarity("naruto", "narto") - (1.0 - 1.0 / 6.0)).abs() < 1e-12);
+    assert!((Levenshtein.similarity("ca", "abc") - (1.0 - 3.0 / 3.0)).abs() < 1e-12);
+    assert!((DamerauLevenshtein.similarity("ca", "abc") - (1.0 - 2.0 / 3.0)).abs() < 1e-12);
+    assert!((DamerauLevenshtein.similarity("naruto", "naurto") - (1.0 - 1.0 / 6.0)).abs() < 1e-12);
+    assert!((Levenshtein.similarity("進撃の巨人", "進撃の巨大") - 0.8).abs() < 1e-12);
+}
+
+#[test]
+fn token_ratios_ignore_word_order() {
+    let a = "kimetsu no yaiba demon slayer";
+    let b = "demon slayer kimetsu no yaiba";
+
+    assert_eq!(TokenSortRatio.similarity(a, b), 1.0);
+    assert_eq!(TokenSetRatio.similarity(a, b), 1.0);
+    assert!(JaroWinkler::default().similarity(a, b) < 0.8);
+
+    assert_eq!(TokenSetRatio.similarity("demon slayer", "kimetsu no yaiba demon slayer"), 1.0);
+    assert!(TokenSortRatio.similarity("demon slayer", "kimetsu no yaiba demon slayer") < 1.0);
+}
+
+#[test]
+fn ngram_overlap() {
+    assert_eq!(NgramJaccard::default().similarity("naruto", "naruto"), 1.0);
+    assert_eq!(NgramJaccard::default().similarity("abcd", "wxyz"), 0.0);
+    // "nar", "aru", "rut" shared out of five distinct trigrams
+    assert!((NgramJaccard::default().similarity("naruto", "narut") - 3.0 / 4.0).abs() < 1e-12);
+    assert!((NgramCosine { n: 2 }.similarity("aab", "ab") - (1.0 / 2f64.sqrt())).abs() < 1e-12);
+}
+
+#[test]
+fn empty_titles_only_match_each_other() {
+    for metric in metrics() {
+        assert_eq!(metric.similarity("", "naruto"), 0.0, "{}", metric.name());
+        assert_eq!(metric.similarity("naruto", ""), 0.0, "{}", metric.name());
+        assert_eq!(metric.similarity("", ""), 1.0, "{}", metric.name());
+    }
+
+    // "(TV)" sanitizes to an empty title
+    let catalogue = catalogue();
+    let search = ExpectAnime::from_string_title("(TV)".into(), None, None);
+    let config = MatcherConfig::new().with_similarity(TokenSetRatio);
+    assert!(find_best_match_with(&search, &catalogue, |anime| anime, &config).is_none());
+}
+
+#[test]
+fn closures_are_metrics() {
+    let exact = |a: &str, b: &str| if a == b { 1.0 } else { 0.0 };
+
+    assert_eq!(exact.similarity("a", "a"), 1.0);
+    assert_eq!(exact.similarity("a", "b"), 0.0);
+}
+
+#[test]
+fn metric_is_selectable_per_tier() {
+    let catalogue = catalogue();
+    let search = ExpectAnime::from_string_title("Kimetsu no Yaiba: Demon Slayer".into(), None, None);
+    let config = MatcherConfig::new()
+        .with_tiers([MatchMethod::Loose])
+        .with_tier_similarity(MatchMethod::Loose, TokenSortRatio);
+
+    let m = find_best_match_with(&search, &catalogue, |anime| anime, &config).unwrap();
+
+    assert_eq!(id_of(&m.result), 2);
+    assert_eq!(m.similarity, 1.0);
+    assert_eq!(config.similarity(MatchMethod::Loose).name(), "token_sort_ratio");
+    assert_eq!(config.similarity(MatchMethod::LastResort).name(), "jaro_winkler");
+}
+
+proptest! {
+    #[test]
+    fn symmetric(a in mixed_script(), b in mixed_script()) {
+        let ab = jaro_winkler_distance(&a, &b, None);
+        let ba = jaro_winkler_distance(&b, &a, None);
+        prop_assert!((ab - ba).abs() < 1e-12, "{} vs {}", ab, ba);
+
+        let ab = jaro_winkler_graphemes(&a, &b, None);
+        let ba = jaro_winkler_graphemes(&b, &a, None);
+        prop_assert!((ab - ba).abs() < 1e-12, "{} vs {}", ab, ba);
+    }
+
+    #[test]
+    fn within_unit_range(a in mixed_script(), b in mixed_script(), p in 0.0f64..0.5) {
+        let score = jaro_winkler_distance(&a, &b, Some(p));
+        prop_assert!((0.0..=1.0).contains(&score), "{}", score);
+
+        let score = jaro_winkler_graphemes(&a, &b, Some(p));
+        prop_assert!((0.0..=1.0).contains(&score), "{}", score);
+    }
+
+    #[test]
+    fn identity(a in mixed_script()) {
+        prop_assert_eq!(jaro_winkler_distance(&a, &a, None), 1.0);
+        prop_assert_eq!(jaro_winkler_graphemes(&a, &a, None), 1.0);
+    }
+
+    #[test]
+    fn metrics_are_symmetric_and_bounded(a in mixed_script(), b in mixed_script()) {
+        for metric in metrics() {
+            let ab = metric.similarity(&a, &b);
+            let ba = metric.similarity(&b, &a);
+            prop_assert!((0.0..=1.0).contains(&ab), "{} gave {}", metric.name(), ab);
+            prop_assert!((ab - ba).abs() < 1e-9, "{} gave {} vs {}", metric.name(), ab, ba);
+            prop_assert_eq!(metric.similarity(&a, &a), 1.0, "{}", metric.name());
+            if a.trim().is_empty() != b.trim().is_empty() && metric.name().starts_with("token") {
+                prop_assert_eq!(ab, 0.0, "{}", metric.name());
+            }
+        }
+    }
+}
@@ -0,0 +1,109 @@
+mod common;
+
+use am_algorithm::{
+    AnimeTitle, ExpectAnime, MatchIndex, MatchMethod, MatcherConfig, TitleField, find_best_match,
+    match_title,
+};
+use common::{catalogue, id_of};
+
+fn with_synonyms() -> Vec<ExpectAnime> {
+    let mut catalogue = catalogue();
+    catalogue[0].title.as_mut().unwrap().synonyms = vec!["AOT".into(), "SnK".into()];
+    catalogue[4].title.as_mut().unwrap().synonyms = vec!["FMA Brotherhood".into()];
+    catalogue[7].title.as_mut().unwrap().synonyms = vec!["Bleach TYBW".into()];
+    catalogue
+}
+
+#[test]
+fn matches_synonyms() {
+    let catalogue = with_synonyms();
+
+    let result = match_title("AOT", &catalogue).unwrap();
+    assert_eq!(id_of(&result.result), 1);
+    assert_eq!(result.method, MatchMethod::Exact);
+    assert_eq!(result.candidate_title.as_deref(), Some("AOT"));
+    assert_eq!(result.matched_field, Some(TitleField::Synonym));
+
+    let result = match_title("fma brotherhood", &catalogue).unwrap();
+    assert_eq!(id_of(&result.result), 5);
+    assert_eq!(result.method, MatchMethod::ExactNormalized);
+    assert_eq!(result.matched_field, Some(TitleField::Synonym));
+
+    let result = match_title("Bleach TYBV", &catalogue).unwrap();
+    assert_eq!(id_of(&result.result), 8);
+    assert_eq!(result.candidate_title.as_deref(), Some("Bleach TYBW"));
+}
+
+#[test]
+fn reports_the_matched_field() {
+    let catalogue = with_synonyms();
+
+    for (title, field) in [
+        ("Attack on Titan", TitleField::English),
+        ("Shingeki no Kyojin", TitleField::UserPreferred),
+        ("進撃の巨人", TitleField::Native),
+        ("SnK", TitleField::Synonym),
+    ] {
+        let result = match_title(title, &catalogue).unwrap();
+        assert_eq!(id_of(&result.result), 1, "{title}");
+        assert_eq!(result.matched_field, Some(field), "{title}");
+    }
+
+    let mut catalogue = catalogue;
+    catalogue[0].title.as_mut().unwrap().user_preferred = None;
+    let result = match_title("Shingeki no Kyojin", &catalogue).unwrap();
+    assert_eq!(result.matched_field, Some(TitleField::Romaji));
+}
+
+#[test]
+fn main_titles_win_ties_with_synonyms() {
+    let mut catalogue = with_synonyms();
+    // Another show abbreviated as the first one's English title
+    catalogue[6].title.as_mut().unwrap().synonyms = vec!["Attack on Titan".into()];
+
+    let result = match_title("Attack on Titan", &catalogue).unwrap();
+    assert_eq!(id_of(&result.result), 1);
+    assert_eq!(result.matched_field, Some(TitleField::English));
+}
+
+#[test]
+fn search_synonyms_are_used() {
+    let catalogue = with_synonyms();
+    let search = ExpectAnime {
+        id: None,
+        title: Some(AnimeTitle {
+            english: Some("Zzzzqqq".into()),
+            synonyms: vec!["Naruto Shippuden".into()],
+            ..AnimeTitle::default()
+        }),
+        year: None,
+        episodes: None,
+        format: None,
+    };
+
+    let result = find_best_match(&search, &catalogue, |anime| anime).unwrap();
+    assert_eq!(id_of(&result.result), 6);
+    assert_eq!(result.title.as_deref(), Some("Naruto Shippuden"));
+
+    let config = MatcherConfig::default();
+    let index = MatchIndex::new(config.normalizer().normalize_catalogue(&catalogue, |anime| anime));
+    assert_eq!(id_of(&index.find_best_match(&search, &config).unwrap().result), 6);
+}
+
+#[test]
+fn synonyms_default_when_missing() {
+    let json = r#"{"english":"Naruto","romaji":null,"native":null,"user_preferred":null}"#;
+    let title: AnimeTitle = serde_json::from_str(json).unwrap();
+    assert!(title.synonyms.is_empty());
+
+    let json = serde_json::to_value(&title).unwrap();
+    assert!(json.get("synonyms").is_none());
+
+    let title = AnimeTitle {
+        synonyms: vec!["AOT".into()],
+        ..AnimeTitle::default()
+    };
+    let json = serde_json::to_value(&title).unwrap();
+    assert_eq!(json["synonyms"], serde_json::json!(["AOT"]));
+    assert_eq!(serde_json::from_value::<AnimeTitle>(json).unwrap().synonyms, ["AOT"]);
+}
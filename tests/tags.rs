@@ -0,0 +1,92 @@
+mod common;
+
+use am_algorithm::{ExpectAnime, MatchMethod, Normalizer, find_best_match, sanitize_title};
+use common::{anime, id_of};
+
+#[test]
+fn strips_each_default_tag() {
+    for (title, expected) in [
+        ("Naruto (TV)", "naruto"),
+        ("Naruto (2002)", "naruto"),
+        ("Naruto BD", "naruto"),
+        ("Naruto [BDRip]", "naruto"),
+        ("Naruto Blu-ray", "naruto"),
+        ("Naruto DVD", "naruto"),
+        ("Naruto DVDRip", "naruto"),
+        ("Naruto WEB-DL", "naruto"),
+        ("Naruto WEBRip", "naruto"),
+        ("Naruto 480p", "naruto"),
+        ("Naruto 720p", "naruto"),
+        ("Naruto 1080p", "naruto"),
+        ("Naruto 2160p", "naruto"),
+        ("Naruto 4K", "naruto"),
+        ("Naruto HEVC", "naruto"),
+        ("Naruto AVC", "naruto"),
+        ("Naruto x264", "naruto"),
+        ("Naruto x265", "naruto"),
+        ("Naruto 10bit", "naruto"),
+        ("Naruto Dual Audio", "naruto"),
+        ("Naruto Dual-Audio", "naruto"),
+        ("Naruto Multi Audio", "naruto"),
+        ("Naruto Uncut", "naruto"),
+        ("Naruto Uncensored", "naruto"),
+        ("Naruto Censored", "naruto"),
+        ("Naruto Dub", "naruto"),
+        ("Naruto Dubbed", "naruto"),
+        ("Naruto Sub", "naruto"),
+        ("Naruto Subbed", "naruto"),
+        ("Shingeki no Kyojin: The Final Chapters", "shingeki no kyojin"),
+    ] {
+        assert_eq!(sanitize_title(Some(title)).as_deref(), Some(expected), "{title}");
+    }
+}
+
+#[test]
+fn tags_only_match_whole_words() {
+    for (title, expected) in [
+        ("Subarashii", "subarashi"),
+        ("Webtoon", "webtoon"),
+        ("TV Tropes", "tv tropes"),
+        ("The Web", "the web"),
+        ("Naruto TV", "naruto tv"),
+        ("Bdo", "bdo"),
+    ] {
+        assert_eq!(sanitize_title(Some(title)).as_deref(), Some(expected), "{title}");
+    }
+}
+
+#[test]
+fn longer_tags_win() {
+    assert_eq!(sanitize_title(Some("Naruto WEB-DL 1080p")).as_deref(), Some("naruto"));
+    assert_eq!(sanitize_title(Some("Naruto BDRip x265 Dual Audio")).as_deref(), Some("naruto"));
+}
+
+#[test]
+fn tag_list_is_configurable() {
+    let normalizer = Normalizer::new().with_tags(["Remastered", "Director's Cut"]);
+
+    assert_eq!(normalizer.tags(), ["Remastered", "Director's Cut"]);
+    assert_eq!(normalizer.sanitize("Akira Remastered"), "akira");
+    assert_eq!(normalizer.sanitize("Akira Director's Cut"), "akira");
+    assert_eq!(normalizer.sanitize("Akira BD"), "akira bd");
+    assert_eq!(normalizer.sanitize("Akira (1988)"), "akira");
+
+    let normalizer = Normalizer::new().with_tags(Vec::<String>::new());
+    assert!(normalizer.tags().is_empty());
+    assert_eq!(normalizer.sanitize("Akira 1080p (1988)"), "akira 1080p");
+
+    assert_eq!(Normalizer::new().tags().len(), Normalizer::DEFAULT_TAGS.len());
+}
+
+#[test]
+fn parenthesized_year_no_longer_hides_a_match() {
+    let catalogue = vec![
+        anime(1, "Fruits Basket", "Fruits Basket", "フルーツバスケット", 2001, 26),
+        anime(2, "Fruits Basket (2019)", "Fruits Basket (2019)", "フルーツバスケット", 2019, 25),
+    ];
+    let search = ExpectAnime::from_string_title("Fruits Basket".into(), Some(2019), Some(25));
+
+    let m = find_best_match(&search, &catalogue, |anime| anime).unwrap();
+    assert_eq!(id_of(&m.result), 2);
+    assert_eq!(m.method, MatchMethod::ExactYearEpisodeNormalized);
+}
@@ -0,0 +1,163 @@
+mod common;
+
+use am_algorithm::{
+    AnimeTitle, ExpectAnime, MatchMethod, MatcherConfig, TitleField, TitleWeights, explain, find_best_match_with,
+    jaro_winkler_distance,
+};
+use common::{catalogue, id_of};
+
+fn titled(id: i64, english: &str, romaji: &str) -> ExpectAnime {
+    ExpectAnime {
+        id: Some(serde_json::json!(id)),
+        title: Some(AnimeTitle {
+            english: Some(english.into()),
+            romaji: Some(romaji.into()),
+            ..AnimeTitle::default()
+        }),
+        year: None,
+        episodes: None,
+        format: None,
+    }
+}
+
+fn same_language() -> MatcherConfig {
+    MatcherConfig::default().with_title_weights(TitleWeights {
+        same_language: true,
+        ..TitleWeights::default()
+    })
+}
+
+#[test]
+fn same_language_avoids_cross_language_hits() {
+    let catalogue = vec![titled(1, "Blue Lock", "Buru Rokku"), titled(2, "Blue Period", "Blue Rock")];
+    let search = ExpectAnime::from_string_title("Blue Rockk".into(), None, None);
+
+    let result = find_best_match_with(&search, &catalogue, |anime| anime, &MatcherConfig::default()).unwrap();
+    assert_eq!(id_of(&result.result), 2);
+    assert_eq!(result.matched_field, Some(TitleField::Romaji));
+
+    let result = find_best_match_with(&search, &catalogue, |anime| anime, &same_language()).unwrap();
+    assert_eq!(id_of(&result.result), 1);
+    assert_eq!(result.matched_field, Some(TitleField::English));
+}
+
+#[test]
+fn same_language_still_compares_untyped_titles() {
+    let mut catalogue = catalogue();
+    catalogue[1].title.as_mut().unwrap().english = None;
+
+    let search = ExpectAnime::from_string_title("Kimetsu no Yaibba".into(), None, None);
+    let result = find_best_match_with(&search, &catalogue, |anime| anime, &same_language()).unwrap();
+    assert_eq!(id_of(&result.result), 2);
+    assert_eq!(result.matched_field, Some(TitleField::UserPreferred));
+
+    // Only the romaji title is left, which an English search is not compared with
+    catalogue[1].title.as_mut().unwrap().user_preferred = None;
+    let result = find_best_match_with(&search, &catalogue, |anime| anime, &same_language());
+    assert!(result.is_none_or(|m| id_of(&m.result) != 2));
+}
+
+#[test]
+fn weights_scale_fuzzy_similarity() {
+    let catalogue = catalogue();
+    let search = ExpectAnime::from_string_title("進撃の巨".into(), None, None);
+    let unweighted = jaro_winkler_distance("進撃の巨", "進撃の巨人", None);
+
+    let result = find_best_match_with(&search, &catalogue, |anime| anime, &MatcherConfig::default()).unwrap();
+    assert_eq!(id_of(&result.result), 1);
+    assert_eq!(result.similarity, unweighted);
+
+    let weights = TitleWeights {
+        native: 0.9,
+        ..TitleWeights::default()
+    };
+    let config = MatcherConfig::default().with_title_weights(weights);
+    let result = find_best_match_with(&search, &catalogue, |anime| anime, &config).unwrap();
+    assert_eq!(result.similarity, unweighted * 0.9);
+    assert_eq!(result.method, MatchMethod::Loose);
+
+    let weights = TitleWeights {
+        native: 0.5,
+        ..TitleWeights::default()
+    };
+    let config = MatcherConfig::default().with_title_weights(weights);
+    assert!(find_best_match_with(&search, &catalogue, |anime| anime, &config).is_none());
+}
+
+#[test]
+fn weighted_scores_stay_in_range() {
+    let catalogue = catalogue();
+    let search = ExpectAnime::from_string_title("進撃の巨".into(), None, None);
+
+    let weights = TitleWeights {
+        native: 1.5,
+        ..TitleWeights::default()
+    };
+    let config = MatcherConfig::default().with_title_weights(weights);
+    let result = find_best_match_with(&search, &catalogue, |anime| anime, &config).unwrap();
+    assert_eq!(id_of(&result.result), 1);
+    assert_eq!(result.similarity, 1.0);
+
+    let explanation = explain(&search, &catalogue, |anime| anime, &config);
+    let pairs = explanation.tiers.iter().flat_map(|tier| &tier.candidates).flat_map(|candidate| &candidate.pairs);
+    assert!(pairs.clone().all(|pair| (0.0..=1.0).contains(&pair.score)));
+
+    // A negative weight leaves the field out rather than scoring below zero
+    let weights = TitleWeights {
+        native: -1.0,
+        ..TitleWeights::default()
+    };
+    let config = MatcherConfig::default().with_title_weights(weights);
+    assert!(find_best_match_with(&search, &catalogue, |anime| anime, &config).is_none());
+
+    let explanation = explain(&search, &catalogue, |anime| anime, &config);
+    let mut pairs = explanation.tiers.iter().flat_map(|tier| &tier.candidates).flat_map(|candidate| &candidate.pairs);
+    assert!(pairs.all(|pair| pair.candidate_field != TitleField::Native || pair.score == 0.0));
+}
+
+#[test]
+fn weights_break_ties_between_fields() {
+    let catalogue = vec![titled(1, "Monster", "Monstre"), titled(2, "Monstre", "Monster")];
+    let search = ExpectAnime::from_string_title("Monsterr".into(), None, None);
+
+    let result = find_best_match_with(&search, &catalogue, |anime| anime, &MatcherConfig::default()).unwrap();
+    assert_eq!(id_of(&result.result), 1);
+
+    let weights = TitleWeights {
+        english: 0.95,
+        ..TitleWeights::default()
+    };
+    let config = MatcherConfig::default().with_title_weights(weights);
+    let result = find_best_match_with(&search, &catalogue, |anime| anime, &config).unwrap();
+    assert_eq!(id_of(&result.result), 2);
+    assert_eq!(result.matched_field, Some(TitleField::Romaji));
+}
+
+#[test]
+fn exact_tiers_ignore_weights() {
+    let catalogue = catalogue();
+    let weights = TitleWeights {
+        native: 0.0,
+        same_language: true,
+        ..TitleWeights::default()
+    };
+    let config = MatcherConfig::default().with_title_weights(weights);
+
+    let search = ExpectAnime::from_string_title("進撃の巨人".into(), None, None);
+    let result = find_best_match_with(&search, &catalogue, |anime| anime, &config).unwrap();
+    assert_eq!(result.method, MatchMethod::Exact);
+    assert_eq!(result.similarity, 1.0);
+}
+
+#[test]
+fn explain_skips_pairs_across_languages() {
+    let catalogue = vec![titled(1, "Blue Lock", "Buru Rokku")];
+    let search = ExpectAnime::from_string_title("Blue Rockk".into(), None, None);
+
+    let explanation = explain(&search, &catalogue, |anime| anime, &same_language());
+    let loose = explanation.tiers.iter().find(|tier| tier.method == MatchMethod::Loose).unwrap();
+    let pairs = &loose.candidates[0].pairs;
+
+    assert_eq!(pairs.len(), 1);
+    assert_eq!(pairs[0].candidate_field, TitleField::English);
+}
@@ -0,0 +1,158 @@
+mod common;
+
+use am_algorithm::{
+    AnimeTitle, ExpectAnime, MatchIndex, MatchMethod, MatcherConfig, TitleField, explain,
+    find_best_match_with, find_matches_with, kana_to_romaji,
+};
+use common::id_of;
+
+#[test]
+fn transliterates_kana_to_hepburn() {
+    for (kana, romaji) in [
+        ("なると", "naruto"),
+        ("ナルト", "naruto"),
+        ("ｼｭﾀｲﾝｽﾞ･ｹﾞｰﾄ", "shutainzugeto"),
+        ("ちゃ", "cha"),
+        ("きょう", "kyou"),
+        ("じゅじゅつ", "jujutsu"),
+        ("しっぷう", "shippuu"),
+        ("まっちゃ", "matcha"),
+        ("ふぁいと", "faito"),
+        ("ヴァイオレット", "vaioretto"),
+        ("ティーン", "tin"),
+        ("こゝろ", "kokoro"),
+        ("を", "o"),
+    ] {
+        assert_eq!(kana_to_romaji(kana), romaji, "{kana}");
+    }
+}
+
+#[test]
+fn keeps_kanji_and_latin_apart() {
+    assert_eq!(kana_to_romaji("ナルト 疾風伝"), "naruto 疾風伝");
+    assert_eq!(kana_to_romaji("ナルト疾風伝"), "naruto 疾風伝");
+    assert_eq!(kana_to_romaji("進撃の巨人"), "進撃 no 巨人");
+    assert_eq!(kana_to_romaji("Attack on Titan"), "Attack on Titan");
+}
+
+fn romaji_only(id: i64, romaji: &str) -> ExpectAnime {
+    ExpectAnime {
+        id: Some(serde_json::json!(id)),
+        title: Some(AnimeTitle {
+            romaji: Some(romaji.into()),
+            ..Default::default()
+        }),
+        year: None,
+        episodes: None,
+        format: None,
+    }
+}
+
+fn native_search(native: &str) -> ExpectAnime {
+    ExpectAnime {
+        id: None,
+        title: Some(AnimeTitle {
+            native: Some(native.into()),
+            ..Default::default()
+        }),
+        year: None,
+        episodes: None,
+        format: None,
+    }
+}
+
+#[test]
+fn native_search_matches_romaji_candidate() {
+    let catalogue = vec![
+        romaji_only(1, "Shingeki no Kyojin"),
+        romaji_only(2, "Naruto Shippuuden"),
+        romaji_only(3, "Violet Evergarden"),
+    ];
+    let search = native_search("ナルト しっぷうでん");
+
+    assert!(find_best_match_with(&search, &catalogue, |anime| anime, &MatcherConfig::default()).is_none());
+
+    let config = MatcherConfig::default().with_tier(MatchMethod::Transliterated);
+    let m = find_best_match_with(&search, &catalogue, |anime| anime, &config).unwrap();
+
+    assert_eq!(id_of(&m.result), 2);
+    assert_eq!(m.method, MatchMethod::Transliterated);
+    assert_eq!(m.normalized.as_deref(), Some("naruto shippuden"));
+    assert_eq!(m.candidate_title.as_deref(), Some("Naruto Shippuuden"));
+}
+
+#[test]
+fn transliterated_tier_runs_last() {
+    let config = MatcherConfig::default().with_tier(MatchMethod::Transliterated);
+
+    assert_eq!(config.tiers().len(), MatchMethod::ALL.len() + 1);
+    assert_eq!(config.tiers().last(), Some(&MatchMethod::Transliterated));
+    assert_eq!(config.clone().with_tier(MatchMethod::Transliterated).tiers(), config.tiers());
+    assert!(!MatchMethod::ALL.contains(&MatchMethod::Transliterated));
+}
+
+#[test]
+fn threshold_applies() {
+    let catalogue = vec![romaji_only(1, "Naruto Shippuuden")];
+    let search = native_search("ナルト しっぷうでん");
+    let config = MatcherConfig::default()
+        .with_tiers([MatchMethod::Transliterated])
+        .with_transliterated_threshold(1.0);
+
+    assert_eq!(config.threshold(MatchMethod::Transliterated), 1.0);
+    assert!(find_best_match_with(&search, &catalogue, |anime| anime, &config).is_some());
+
+    let search = native_search("ナルト しっぷ");
+    assert!(find_best_match_with(&search, &catalogue, |anime| anime, &config).is_none());
+}
+
+#[test]
+fn searches_without_kana_are_not_transliterated() {
+    let catalogue = vec![romaji_only(1, "Naruto Shippuuden")];
+    let config = MatcherConfig::default().with_tiers([MatchMethod::Transliterated]);
+
+    let search = ExpectAnime::from_string_title("Naruto Shippuuden".into(), None, None);
+    assert!(find_best_match_with(&search, &catalogue, |anime| anime, &config).is_none());
+    assert!(explain(&search, &catalogue, |anime| anime, &config).search.transliterated.is_none());
+}
+
+#[test]
+fn index_and_explain_agree() {
+    let catalogue = vec![
+        romaji_only(1, "Shingeki no Kyojin"),
+        romaji_only(2, "Naruto Shippuuden"),
+    ];
+    let search = native_search("ナルト しっぷうでん");
+    let config = MatcherConfig::default().with_tier(MatchMethod::Transliterated);
+    let index = MatchIndex::new(config.normalizer().normalize_catalogue(&catalogue, |anime| anime));
+
+    let expected = find_matches_with(&search, &catalogue, 2, |anime| anime, &config);
+    let actual = index.find_matches(&search, 2, &config);
+    assert_eq!(expected.len(), 1);
+    assert_eq!(
+        expected.iter().map(|m| (id_of(&m.result), m.method)).collect::<Vec<_>>(),
+        actual.iter().map(|m| (id_of(&m.result), m.method)).collect::<Vec<_>>()
+    );
+
+    let explanation = explain(&search, &catalogue, |anime| anime, &config);
+    let winner = explanation.winner.unwrap();
+    assert_eq!(winner.method, MatchMethod::Transliterated);
+    assert_eq!(winner.index, 1);
+
+    let tier = explanation.tiers.last().unwrap();
+    let pair = &tier.candidates[1].pairs[0];
+    assert_eq!(pair.search_field, TitleField::Romaji);
+    assert_eq!(pair.search_title, kana_to_romaji("ナルト しっぷうでん"));
+    assert_eq!(
+        pair.normalized,
+        Some(("naruto shippuden".to_string(), "naruto shippuden".to_string()))
+    );
+
+    // The search trace shows the kana title and what the tier compared it as
+    assert_eq!(explanation.search.titles, ["ナルト しっぷうでん"]);
+    assert_eq!(explanation.search.fields, [TitleField::Native]);
+    let transliterated = explanation.search.transliterated.unwrap();
+    assert_eq!(transliterated.fields, [TitleField::Romaji]);
+    assert_eq!(transliterated.titles, [kana_to_romaji("ナルト しっぷうでん")]);
+    assert_eq!(transliterated.normalized_titles, ["naruto shippuden"]);
+}
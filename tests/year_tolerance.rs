@@ -0,0 +1,114 @@
+mod common;
+
+use am_algorithm::{
+    ExpectAnime, MatchMethod, MatcherConfig, YearMatch, find_best_match, find_best_match_with,
+    find_matches_with,
+};
+use common::{anime, catalogue, id_of};
+
+#[test]
+fn exact_year_is_required_by_default() {
+    let catalogue = catalogue();
+    let search = ExpectAnime::from_string_title("Attack on Titan".into(), Some(2014), Some(25));
+
+    let m = find_best_match(&search, &catalogue, |anime| anime).unwrap();
+
+    assert_eq!(m.method, MatchMethod::Exact);
+    assert_eq!(m.year_match, None);
+}
+
+#[test]
+fn exact_year_is_flagged() {
+    let catalogue = catalogue();
+    let search = ExpectAnime::from_string_title("Attack on Titan".into(), Some(2013), None);
+
+    let m = find_best_match(&search, &catalogue, |anime| anime).unwrap();
+
+    assert_eq!(m.method, MatchMethod::ExactYearRaw);
+    assert_eq!(m.year_match, Some(YearMatch::Exact));
+    assert_eq!(m.similarity, 1.0);
+}
+
+#[test]
+fn tolerance_accepts_nearby_years_with_a_penalty() {
+    let catalogue = catalogue();
+    let search = ExpectAnime::from_string_title("Attack on Titan".into(), Some(2014), Some(25));
+    let config = MatcherConfig::new().with_year_tolerance(1).with_year_penalty(0.1);
+
+    let m = find_best_match_with(&search, &catalogue, |anime| anime, &config).unwrap();
+
+    assert_eq!(m.method, MatchMethod::ExactYearEpisodeRaw);
+    assert_eq!(m.year_match, Some(YearMatch::Within(1)));
+    assert!((m.similarity - 0.9).abs() < 1e-12);
+}
+
+#[test]
+fn penalties_cannot_sink_an_exact_match_below_the_floor() {
+    let catalogue = catalogue();
+    let search = ExpectAnime::from_string_title("Attack on Titan".into(), Some(2016), None);
+
+    // Three years off at 0.05 a year stays above the floor
+    let config = MatcherConfig::new().with_year_tolerance(3);
+    let m = find_best_match_with(&search, &catalogue, |anime| anime, &config).unwrap();
+    assert_eq!(m.method, MatchMethod::ExactYearRaw);
+    assert!((m.similarity - 0.85).abs() < 1e-12);
+
+    // At 0.5 a year the year-aware exact tiers give up instead of matching at 0.0
+    let config = MatcherConfig::new().with_year_tolerance(3).with_year_penalty(0.5);
+    assert_eq!(config.exact_penalty_floor(), 0.8);
+    let m = find_best_match_with(&search, &catalogue, |anime| anime, &config).unwrap();
+    assert_eq!(id_of(&m.result), 1);
+    assert_eq!(m.method, MatchMethod::Exact);
+    assert_eq!(m.similarity, 1.0);
+
+    let config = config.with_exact_penalty_floor(0.0);
+    let m = find_best_match_with(&search, &catalogue, |anime| anime, &config).unwrap();
+    assert_eq!(m.method, MatchMethod::ExactYearRaw);
+    assert_eq!(m.similarity, 0.0);
+}
+
+#[test]
+fn tolerance_is_a_hard_limit() {
+    let catalogue = catalogue();
+    let search = ExpectAnime::from_string_title("Attack on Titan".into(), Some(2015), Some(25));
+    let config = MatcherConfig::new().with_year_tolerance(1);
+
+    let m = find_best_match_with(&search, &catalogue, |anime| anime, &config).unwrap();
+
+    assert_eq!(m.method, MatchMethod::Exact);
+}
+
+#[test]
+fn exact_year_beats_an_earlier_candidate_within_tolerance() {
+    let catalogue = vec![
+        anime(1, "Hunter x Hunter", "Hunter x Hunter", "ハンター×ハンター", 1999, 62),
+        anime(2, "Hunter x Hunter", "Hunter x Hunter (2011)", "HUNTER×HUNTER", 2011, 148),
+        anime(3, "Hunter x Hunter", "Hunter x Hunter", "ハンター×ハンター", 2012, 148),
+    ];
+    let search = ExpectAnime::from_string_title("Hunter x Hunter".into(), Some(2012), None);
+    let config = MatcherConfig::new().with_year_tolerance(1);
+
+    let m = find_best_match_with(&search, &catalogue, |anime| anime, &config).unwrap();
+    assert_eq!(id_of(&m.result), 3);
+    assert_eq!(m.year_match, Some(YearMatch::Exact));
+
+    let ranked = find_matches_with(&search, &catalogue, 3, |anime| anime, &config);
+    assert_eq!(ranked.iter().map(|m| id_of(&m.result)).collect::<Vec<_>>(), vec![3, 2, 1]);
+    assert_eq!(ranked[1].year_match, Some(YearMatch::Within(1)));
+    assert_eq!(ranked[2].method, MatchMethod::Exact);
+}
+
+#[test]
+fn penalty_applies_before_the_fuzzy_threshold() {
+    let catalogue = catalogue();
+    let search = ExpectAnime::from_string_title("Kimetsu no Yaibba".into(), Some(2020), None);
+    let lenient = MatcherConfig::new().with_year_tolerance(1).with_year_penalty(0.0);
+    let harsh = MatcherConfig::new().with_year_tolerance(1).with_year_penalty(0.5);
+
+    let m = find_best_match_with(&search, &catalogue, |anime| anime, &lenient).unwrap();
+    assert_eq!(m.method, MatchMethod::LooseYear);
+    assert_eq!(m.year_match, Some(YearMatch::Within(1)));
+
+    let m = find_best_match_with(&search, &catalogue, |anime| anime, &harsh).unwrap();
+    assert_eq!(m.method, MatchMethod::Loose);
+}